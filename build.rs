@@ -0,0 +1,56 @@
+//! Target detection
+//!
+//! Emits `armv6m` for Cortex-M0/M0+ targets, which have no DWT cycle
+//! counter (the same cfg the `cortex-m` crate uses internally).
+//!
+//! Also reads the RAM length from `memory.x`, so `board::RAM_SIZE` follows
+//! whichever part the linker script describes.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=memory.x");
+    println!("cargo:rustc-check-cfg=cfg(armv6m)");
+
+    let target = env::var("TARGET").unwrap_or_default();
+    if target.starts_with("thumbv6m-") {
+        println!("cargo:rustc-cfg=armv6m");
+    }
+
+    let script = fs::read_to_string("memory.x").expect("memory.x not found");
+    let ram = ram_length(&script).expect("memory.x has no `RAM : ... LENGTH = ...` region");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("memory.rs");
+    fs::write(
+        out,
+        format!("/// RAM length from `memory.x`\npub const RAM_SIZE: usize = {ram};\n"),
+    )
+    .unwrap();
+}
+
+/// `LENGTH` of the `RAM` region, e.g. `128K`, `0x20000` or `1M`
+fn ram_length(script: &str) -> Option<usize> {
+    script
+        .lines()
+        .map(|line| line.split("/*").next().unwrap_or(""))
+        .find(|line| line.split(':').next().map(str::trim) == Some("RAM"))
+        .and_then(|line| line.split("LENGTH").nth(1))
+        .map(|rest| {
+            rest.trim_start_matches([' ', '='])
+                .trim_end_matches([' ', ','])
+        })
+        .and_then(parse_size)
+}
+
+fn parse_size(value: &str) -> Option<usize> {
+    let (digits, scale) = match value.as_bytes().last()? {
+        b'K' | b'k' => (&value[..value.len() - 1], 1024),
+        b'M' | b'm' => (&value[..value.len() - 1], 1024 * 1024),
+        _ => (value, 1),
+    };
+    let number = match digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok()?,
+        None => digits.parse().ok()?,
+    };
+    Some(number * scale)
+}
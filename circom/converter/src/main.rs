@@ -1,5 +1,108 @@
-use serde::{Deserialize, Serialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::Deserialize;
+use std::fmt::Write;
 use std::fs;
+use std::process;
+use std::str::FromStr;
+
+const USAGE: &str = "usage: snarkjs-to-rust [--vk PATH] [--proof PATH] [--public PATH] [--out PATH] \
+     [--curve bn254|bls12-381] [--compressed]";
+
+/// Pairing curve the key and proof are on
+#[derive(Clone, Copy, PartialEq)]
+enum Curve {
+    Bn254,
+    Bls12_381,
+}
+
+impl Curve {
+    /// Accepts our names and the ones snarkjs writes to `vk.json`
+    fn parse(name: &str) -> Option<Curve> {
+        match name {
+            "bn254" | "bn128" => Some(Curve::Bn254),
+            "bls12-381" | "bls12381" => Some(Curve::Bls12_381),
+            _ => None,
+        }
+    }
+
+    /// Arkworks crate the generated code imports the curve types from
+    fn crate_name(self) -> &'static str {
+        match self {
+            Curve::Bn254 => "ark_bn254",
+            Curve::Bls12_381 => "ark_bls12_381",
+        }
+    }
+}
+
+/// Input and output locations, defaulting to the layout under `circom/`
+struct Paths {
+    vk: String,
+    proof: String,
+    public: String,
+    out: String,
+    curve: Curve,
+    /// Emit points as compressed bytes decoded at run time instead of literals
+    compressed: bool,
+}
+
+impl Paths {
+    fn from_args() -> Paths {
+        let mut paths = Paths {
+            vk: "../vk.json".to_string(),
+            proof: "../proof.json".to_string(),
+            public: "../public.json".to_string(),
+            out: "../../src/utils/vk_proof.rs".to_string(),
+            curve: Curve::Bn254,
+            compressed: false,
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let slot = match flag.as_str() {
+                "--vk" => &mut paths.vk,
+                "--proof" => &mut paths.proof,
+                "--public" => &mut paths.public,
+                "--out" => &mut paths.out,
+                "--curve" => {
+                    let name = args
+                        .next()
+                        .unwrap_or_else(|| fail(&format!("`--curve` needs a name\n{}", USAGE)));
+                    paths.curve = Curve::parse(&name)
+                        .unwrap_or_else(|| fail(&format!("unknown curve `{}`\n{}", name, USAGE)));
+                    continue;
+                }
+                "--compressed" => {
+                    paths.compressed = true;
+                    continue;
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                _ => fail(&format!("unknown argument `{}`\n{}", flag, USAGE)),
+            };
+            *slot = args
+                .next()
+                .unwrap_or_else(|| fail(&format!("`{}` needs a path\n{}", flag, USAGE)));
+        }
+
+        paths
+    }
+}
+
+/// Print an error and exit with a non-zero status
+fn fail(message: &str) -> ! {
+    eprintln!("✗ {}", message);
+    process::exit(1);
+}
+
+/// Read and parse a JSON file, exiting with `hint` if it is missing
+fn read_json<T: serde::de::DeserializeOwned>(path: &str, hint: &str) -> T {
+    let json = fs::read_to_string(path)
+        .unwrap_or_else(|e| fail(&format!("Failed to read {}: {}. {}", path, e, hint)));
+    serde_json::from_str(&json)
+        .unwrap_or_else(|e| fail(&format!("Failed to parse {}: {}", path, e)))
+}
 
 #[derive(Debug, Deserialize)]
 struct VerificationKey {
@@ -13,6 +116,9 @@ struct VerificationKey {
     vk_delta_2: Vec<Vec<String>>,
     #[serde(rename = "IC")]
     ic: Vec<Vec<String>>,
+    #[serde(rename = "nPublic")]
+    n_public: Option<usize>,
+    curve: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,48 +160,102 @@ fn fr_to_rust(x: &str) -> String {
     format!("Fr::from_str(\"{}\").unwrap()", x)
 }
 
-fn main() {
-    println!("Reading snarkjs output files...");
+/// Parse a decimal field element, exiting if it is out of range
+fn parse_field<F: FromStr>(x: &str) -> F {
+    F::from_str(x).unwrap_or_else(|_| fail(&format!("`{}` is not a base field element", x)))
+}
 
-    // Read verification key
-    let vk_json = fs::read_to_string("../vk.json")
-        .expect("Failed to read vk.json. Run setup.sh first.");
-    let vk: VerificationKey = serde_json::from_str(&vk_json)
-        .expect("Failed to parse vk.json");
+/// Serialize a point in arkworks' compressed form and check it decodes back
+///
+/// The firmware decodes with `CanonicalDeserialize::deserialize`, which
+/// rejects points off the curve or outside the subgroup; failing here
+/// instead keeps a bad key from reaching the board.
+fn compress<P: CanonicalSerialize + CanonicalDeserialize + PartialEq>(point: &P, name: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point
+        .serialize(&mut bytes)
+        .unwrap_or_else(|e| fail(&format!("Failed to compress {}: {}", name, e)));
+    match P::deserialize(&bytes[..]) {
+        Ok(decoded) if decoded == *point => bytes,
+        _ => fail(&format!("{} is not a valid curve point", name)),
+    }
+}
 
-    // Read proof
-    let proof_json = fs::read_to_string("../proof.json")
-        .expect("Failed to read proof.json. Run generate_proof.sh first.");
-    let proof: Proof = serde_json::from_str(&proof_json)
-        .expect("Failed to parse proof.json");
+/// Compressed encoder for a snarkjs G1 point `[x, y, z]`
+type G1Encoder = fn(&[String], &str) -> Vec<u8>;
+/// Compressed encoder for a snarkjs G2 point `[[x0, x1], [y0, y1], [z0, z1]]`
+type G2Encoder = fn(&[Vec<String>], &str) -> Vec<u8>;
 
-    // Read public inputs
-    let public_json = fs::read_to_string("../public.json")
-        .expect("Failed to read public.json. Run generate_proof.sh first.");
-    let public_inputs: Vec<String> = serde_json::from_str(&public_json)
-        .expect("Failed to parse public.json");
+/// Point encoders for `--compressed`, one module per curve crate
+macro_rules! point_encoders {
+    ($module:ident, $krate:ident) => {
+        mod $module {
+            use super::{compress, parse_field};
+            use $krate::{Fq2, G1Affine, G2Affine};
 
-    println!("Generating Rust code...");
+            pub fn g1_to_bytes(p: &[String], name: &str) -> Vec<u8> {
+                // Same snarkjs layout as `g1_to_rust`
+                let point = if p.len() >= 3 && p[2] == "0" {
+                    G1Affine::default()
+                } else {
+                    G1Affine::new(parse_field(&p[0]), parse_field(&p[1]), false)
+                };
+                compress(&point, name)
+            }
 
+            pub fn g2_to_bytes(p: &[Vec<String>], name: &str) -> Vec<u8> {
+                // Same snarkjs layout as `g2_to_rust`
+                let point = if p.len() >= 3 && p[2][0] == "0" && p[2][1] == "0" {
+                    G2Affine::default()
+                } else {
+                    G2Affine::new(
+                        Fq2::new(parse_field(&p[0][0]), parse_field(&p[0][1])),
+                        Fq2::new(parse_field(&p[1][0]), parse_field(&p[1][1])),
+                        false,
+                    )
+                };
+                compress(&point, name)
+            }
+        }
+    };
+}
+
+point_encoders!(bn254, ark_bn254);
+point_encoders!(bls12_381, ark_bls12_381);
+
+/// Format bytes as the body of a Rust array literal, 16 per line
+fn bytes_to_rust(bytes: &[u8], indent: &str) -> String {
+    let mut out = String::new();
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        if i > 0 {
+            out.push('\n');
+            out.push_str(indent);
+        }
+        for (j, byte) in chunk.iter().enumerate() {
+            if j > 0 {
+                out.push(' ');
+            }
+            write!(out, "0x{:02x},", byte).unwrap();
+        }
+    }
+    out
+}
+
+/// Generated module with every point as a literal affine coordinate pair
+fn literal_module(curve: Curve, vk: &VerificationKey, proof: &Proof, public_array: &str) -> String {
     // Generate IC array
     let ic_elements: Vec<String> = vk.ic.iter()
         .map(|p| g1_to_rust(p))
         .collect();
     let ic_array = ic_elements.join(",\n    ");
 
-    // Generate public inputs array
-    let public_elements: Vec<String> = public_inputs.iter()
-        .map(|x| fr_to_rust(x))
-        .collect();
-    let public_array = public_elements.join(",\n    ");
-
-    let output = format!(
+    format!(
 r#"// AUTO-GENERATED FROM snarkjs
 // DO NOT EDIT MANUALLY
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
-use ark_bn254::{{Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective}};
+use {krate}::{{Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective}};
 use ark_std::{{str::FromStr, vec::Vec, Zero}};
 
 pub struct Vk {{
@@ -144,12 +304,191 @@ pub fn sample_proof() -> (Proof, Vec<Fr>) {{
         g1_to_rust(&proof.pi_a),
         g2_to_rust(&proof.pi_b),
         g1_to_rust(&proof.pi_c),
-        public_array
-    );
+        public_array,
+        krate = curve.crate_name()
+    )
+}
+
+/// Generated module with every point as compressed bytes
+///
+/// Each point is stored as 32 (G1) or 64 (G2) bytes on BN254, 48 or 96 on
+/// BLS12-381, instead of decimal strings parsed by `from_str`. That is
+/// less rodata per point, but the points are decompressed and
+/// subgroup-checked on every call, and the decoder adds code: for the
+/// sample circuit the firmware came out at 82288 .text bytes against 80476
+/// for `literal_module`, so it only pays off with many public inputs.
+fn compressed_module(curve: Curve, vk: &VerificationKey, proof: &Proof, public_array: &str) -> String {
+    let (g1_to_bytes, g2_to_bytes): (G1Encoder, G2Encoder) = match curve {
+        Curve::Bn254 => (bn254::g1_to_bytes, bn254::g2_to_bytes),
+        Curve::Bls12_381 => (bls12_381::g1_to_bytes, bls12_381::g2_to_bytes),
+    };
+    let alpha_g1 = g1_to_bytes(&vk.vk_alpha_1, "vk_alpha_1");
+    let beta_g2 = g2_to_bytes(&vk.vk_beta_2, "vk_beta_2");
+    let ic_points: Vec<String> = vk.ic.iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let bytes = g1_to_bytes(p, &format!("IC[{}]", i));
+            format!("[\n    {}\n  ]", bytes_to_rust(&bytes, "    "))
+        })
+        .collect();
+
+    format!(
+r#"// AUTO-GENERATED FROM snarkjs (--compressed)
+// DO NOT EDIT MANUALLY
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+use {krate}::{{Fr, G1Affine, G1Projective, G2Affine, G2Projective}};
+use ark_serialize::CanonicalDeserialize;
+use ark_std::{{str::FromStr, vec::Vec}};
+
+// Points in arkworks' compressed encoding, decoded on every call
+const ALPHA_G1: [u8; {g1_len}] = [
+  {}
+];
+const BETA_G2: [u8; {g2_len}] = [
+  {}
+];
+const GAMMA_G2: [u8; {g2_len}] = [
+  {}
+];
+const DELTA_G2: [u8; {g2_len}] = [
+  {}
+];
+const IC: [[u8; {g1_len}]; {}] = [
+  {}
+];
+const PROOF_A: [u8; {g1_len}] = [
+  {}
+];
+const PROOF_B: [u8; {g2_len}] = [
+  {}
+];
+const PROOF_C: [u8; {g1_len}] = [
+  {}
+];
+
+fn g1(bytes: &[u8]) -> G1Projective {{
+  G1Affine::deserialize(bytes).expect("generated G1 point is valid").into()
+}}
+
+fn g2(bytes: &[u8]) -> G2Projective {{
+  G2Affine::deserialize(bytes).expect("generated G2 point is valid").into()
+}}
+
+pub struct Vk {{
+  pub alpha_g1: G1Projective,
+  pub beta_g2: G2Projective,
+  pub gamma_g2: G2Projective,
+  pub delta_g2: G2Projective,
+  pub ic: Vec<G1Projective>,
+}}
+
+pub fn verification_key() -> Vk {{
+  Vk {{
+    alpha_g1: g1(&ALPHA_G1),
+    beta_g2: g2(&BETA_G2),
+    gamma_g2: g2(&GAMMA_G2),
+    delta_g2: g2(&DELTA_G2),
+    ic: IC.iter().map(|p| g1(p)).collect(),
+  }}
+}}
+
+pub struct Proof {{
+  pub a: G1Projective,
+  pub b: G2Projective,
+  pub c: G1Projective,
+}}
+
+pub fn sample_proof() -> (Proof, Vec<Fr>) {{
+  let proof = Proof {{
+    a: g1(&PROOF_A),
+    b: g2(&PROOF_B),
+    c: g1(&PROOF_C),
+  }};
+  let public_inputs = ark_std::vec![
+    {}
+  ];
+  (proof, public_inputs)
+}}
+"#,
+        bytes_to_rust(&alpha_g1, "  "),
+        bytes_to_rust(&beta_g2, "  "),
+        bytes_to_rust(&g2_to_bytes(&vk.vk_gamma_2, "vk_gamma_2"), "  "),
+        bytes_to_rust(&g2_to_bytes(&vk.vk_delta_2, "vk_delta_2"), "  "),
+        ic_points.len(),
+        ic_points.join(",\n  "),
+        bytes_to_rust(&g1_to_bytes(&proof.pi_a, "pi_a"), "  "),
+        bytes_to_rust(&g2_to_bytes(&proof.pi_b, "pi_b"), "  "),
+        bytes_to_rust(&g1_to_bytes(&proof.pi_c, "pi_c"), "  "),
+        public_array,
+        krate = curve.crate_name(),
+        g1_len = alpha_g1.len(),
+        g2_len = beta_g2.len()
+    )
+}
+
+fn main() {
+    let paths = Paths::from_args();
+    println!("Reading snarkjs output files...");
+
+    let vk: VerificationKey = read_json(&paths.vk, "Run setup.sh first.");
+    let proof: Proof = read_json(&paths.proof, "Run generate_proof.sh first.");
+    let public_inputs: Vec<String> = read_json(&paths.public, "Run generate_proof.sh first.");
+
+    // The key has one IC point per public signal plus the constant term
+    println!("Detected {} public signal(s)", public_inputs.len());
+    if vk.ic.len() != public_inputs.len() + 1 {
+        eprintln!(
+            "✗ {} has {} IC points, expected {} for {} public signal(s) in {}.",
+            paths.vk,
+            vk.ic.len(),
+            public_inputs.len() + 1,
+            public_inputs.len(),
+            paths.public
+        );
+        eprintln!("  The key and proof come from different circuits; re-run setup.sh and generate_proof.sh.");
+        process::exit(1);
+    }
+    if let Some(n_public) = vk.n_public {
+        if n_public != public_inputs.len() {
+            eprintln!(
+                "✗ {} declares nPublic = {}, but {} has {} signal(s).",
+                paths.vk,
+                n_public,
+                paths.public,
+                public_inputs.len()
+            );
+            process::exit(1);
+        }
+    }
+    if let Some(name) = &vk.curve {
+        if Curve::parse(name) != Some(paths.curve) {
+            eprintln!(
+                "✗ {} is for curve `{}`; pass a matching --curve.",
+                paths.vk, name
+            );
+            process::exit(1);
+        }
+    }
+
+    println!("Generating Rust code...");
+
+    // Generate public inputs array
+    let public_elements: Vec<String> = public_inputs.iter()
+        .map(|x| fr_to_rust(x))
+        .collect();
+    let public_array = public_elements.join(",\n    ");
+
+    let output = if paths.compressed {
+        compressed_module(paths.curve, &vk, &proof, &public_array)
+    } else {
+        literal_module(paths.curve, &vk, &proof, &public_array)
+    };
 
-    let output_path = "../../src/utils/vk_proof.rs";
+    let output_path = &paths.out;
     fs::write(output_path, output)
-        .expect("Failed to write output file");
+        .unwrap_or_else(|e| fail(&format!("Failed to write {}: {}", output_path, e)));
 
     println!("✓ Generated {}", output_path);
     println!();
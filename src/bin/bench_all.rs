@@ -0,0 +1,137 @@
+#![no_std]
+#![no_main]
+
+use core::hint::black_box;
+
+use cortex_m_rt::entry;
+use panic_rtt_target as _;
+use rtt_target::rtt_init_print;
+use stm32f4xx_hal::{pac, prelude::*};
+
+use stm32_tests::board;
+use stm32_tests::log;
+use stm32_tests::utils::bench::Benchmark;
+use stm32_tests::utils::crypto::{
+    eip712_hash, keccak256, sign_eip712, DemoRng, Eip712Domain, Eip712Struct, EthereumKeyPair,
+    InsecureCryptoRng, MultisigTransaction,
+};
+use stm32_tests::utils::delay::TimerDelay;
+use stm32_tests::utils::falcon::Falcon512KeyPair;
+use stm32_tests::utils::groth16::{self, vk_proof};
+use stm32_tests::utils::led::{BlinkPattern, StatusLed};
+
+/// Fixed seed so every run benchmarks the same keys
+const SEED: u32 = 0x5EED_0001;
+
+#[entry]
+fn main() -> ! {
+    // Initialize RTT for debug output
+    rtt_init_print!();
+
+    // Initialize heap for Falcon512 and arkworks
+    const HEAP_SIZE: usize = 96 * 1024;
+    const _: () = assert!(
+        board::heap_fits(HEAP_SIZE),
+        "heap plus stack reserve exceeds board RAM"
+    );
+    static mut HEAP_MEM: [u8; HEAP_SIZE] = [0; HEAP_SIZE];
+    unsafe {
+        stm32_tests::ALLOCATOR.init(
+            core::ptr::addr_of!(HEAP_MEM) as *const _ as usize,
+            HEAP_SIZE,
+        )
+    }
+
+    // Get device peripherals
+    let dp = pac::Peripherals::take().unwrap();
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+
+    // Run the core from the PLL and keep RCC for enabling GPIO clocks
+    let mut rcc = board::init_clocks(dp.RCC);
+    let mut delay = TimerDelay::new(cp.SYST, &rcc.clocks);
+
+    // Setup GPIOC (PC13 LED)
+    let gpioc = dp.GPIOC.split(&mut rcc);
+    let mut led = StatusLed::new(gpioc.pc13.into_push_pull_output());
+
+    // Route log output to USART1 when built with the `uart` feature
+    #[cfg(feature = "uart")]
+    {
+        let gpioa = dp.GPIOA.split(&mut rcc);
+        stm32_tests::utils::logger::init_uart(gpioa.pa9, dp.USART1, &mut rcc, 115_200);
+    }
+
+    // Enable DWT cycle counter for precise timing
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    log!("=== STM32 Crypto Benchmarks ===");
+    log!("sysclk_hz={}", rcc.clocks.sysclk().raw());
+
+    let mut bench = Benchmark::new();
+    let message = b"Benchmark message";
+
+    // Hashing
+    let block = [0xA5u8; 1024];
+    bench.run("keccak256_1k", 100, || {
+        black_box(keccak256(black_box(&block)));
+    });
+
+    // ECDSA (secp256k1)
+    let keypair = EthereumKeyPair::generate(&mut InsecureCryptoRng::new(DemoRng::new(SEED)));
+    bench.run("ecdsa_sign", 10, || {
+        black_box(keypair.sign_deterministic(black_box(message)));
+    });
+    let (signature, _) = keypair.sign_recoverable(message);
+    bench.run("ecdsa_verify", 10, || {
+        black_box(keypair.verify(black_box(message), &signature).is_ok());
+    });
+
+    // EIP-712
+    let domain = Eip712Domain::with_chain_id_u64("MyMultisig", "1", 1, [0x11; 20]);
+    let transaction = MultisigTransaction::new([0x22; 20], 1_000_000_000_000_000_000, &[], 42);
+    bench.run("eip712_hash", 100, || {
+        black_box(eip712_hash(&domain, &black_box(&transaction).hash_struct()));
+    });
+    bench.run("eip712_sign", 10, || {
+        black_box(sign_eip712(&keypair, &domain, black_box(&transaction)));
+    });
+
+    // Falcon512. Key generation draws millions of words, which DemoRng
+    // supplies cheaply; an EntropyPool spends two Keccak hashes on each.
+    let mut rng = DemoRng::new(SEED);
+    let falcon = Falcon512KeyPair::generate(&mut rng);
+    bench.run("falcon512_sign", 3, || {
+        black_box(falcon.sign(black_box(message), &mut rng));
+    });
+    let falcon_signature = falcon.sign(message, &mut rng);
+    bench.run("falcon512_verify", 10, || {
+        black_box(falcon.verify(black_box(message), &falcon_signature));
+    });
+
+    // Groth16 (BN254)
+    let vk_data = vk_proof::verification_key();
+    let vk = groth16::Vk {
+        alpha_g1: vk_data.alpha_g1,
+        beta_g2: vk_data.beta_g2,
+        gamma_g2: vk_data.gamma_g2,
+        delta_g2: vk_data.delta_g2,
+        ic: &vk_data.ic,
+    };
+    let pvk: groth16::PreparedVk = vk.prepare();
+    let (proof_data, public_inputs) = vk_proof::sample_proof();
+    let proof = groth16::Proof {
+        a: proof_data.a,
+        b: proof_data.b,
+        c: proof_data.c,
+    };
+    bench.run("groth16_verify", 2, || {
+        black_box(groth16::verify_proof_prepared(&pvk, black_box(&proof), &public_inputs).is_ok());
+    });
+
+    log!("Done");
+    BlinkPattern::Success.play(&mut led, &mut delay);
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
@@ -3,81 +3,58 @@
 
 use cortex_m_rt::entry;
 use panic_rtt_target as _;
-use rtt_target::{rprintln, rtt_init_print};
+use rtt_target::rtt_init_print;
 use stm32f4xx_hal::{pac, prelude::*};
 
-// Simple delay function
-fn delay_ms(ms: u32) {
-    for _ in 0..(ms * 2000) {
-        cortex_m::asm::nop();
-    }
-}
-
-// Target count - change this value to test different loop counts
-const TARGET_COUNT: u64 = 10_000_000;
+use stm32_tests::board;
+use stm32_tests::demos::{
+    counter::{CounterDemo, TARGET_COUNTS},
+    run_on_button, DemoContext,
+};
+use stm32_tests::log;
+use stm32_tests::utils::button::Button;
+use stm32_tests::utils::delay::TimerDelay;
+use stm32_tests::utils::led::StatusLed;
+use stm32_tests::utils::timing::CycleTimer;
 
 #[entry]
 fn main() -> ! {
     // Initialize RTT for debug output
     rtt_init_print!();
-    rprintln!("=== STM32 Loop Speed Test ===");
-    rprintln!("Target count: {}", TARGET_COUNT);
-    rprintln!("Press button to start counting loop");
 
     // Get device peripherals
     let dp = pac::Peripherals::take().unwrap();
+    let mut cp = cortex_m::Peripherals::take().unwrap();
 
-    // Get RCC peripheral for enabling GPIO clocks
-    let mut rcc = dp.RCC;
+    // Run the core from the PLL and keep RCC for enabling GPIO clocks
+    let mut rcc = board::init_clocks(dp.RCC);
+
+    // Time demos against the clock just configured
+    let timer = CycleTimer::from_clocks(&rcc.clocks);
 
     // Setup GPIOC (PC13 LED)
     let gpioc = dp.GPIOC.split(&mut rcc);
-    let mut led = gpioc.pc13.into_push_pull_output();
+    let led = StatusLed::new(gpioc.pc13.into_push_pull_output());
+    let mut ctx = DemoContext::new(led, TimerDelay::new(cp.SYST, &rcc.clocks), timer);
 
     // Setup GPIOA (PA0 Button)
     let gpioa = dp.GPIOA.split(&mut rcc);
-    let button = gpioa.pa0.into_pull_up_input();
-
-    // Turn LED off initially (LED is active low on STM32 Blackpill)
-    led.set_high();
-
-    rprintln!("Ready! Press button to start...");
-
-    loop {
-        // Wait for button press
-        while button.is_high() {
-            // Wait for button to be pressed
-        }
-
-        rprintln!("Button pressed! Starting count loop...");
-
-        // Start counting loop - this is the speed test
-        // Use volatile operations to prevent compiler optimization
-        let mut count: u64 = 0;
-        while count < TARGET_COUNT {
-            count += 1;
-            // Use black_box to prevent the compiler from optimizing away the loop
-            core::hint::black_box(&count);
-        }
+    let mut button = Button::new(gpioa.pa0.into_pull_up_input());
 
-        rprintln!("Count reached {}! Blinking LED...", count);
+    // Route log output to USART1 when built with the `uart` feature
+    #[cfg(feature = "uart")]
+    stm32_tests::utils::logger::init_uart(gpioa.pa9, dp.USART1, &mut rcc, 115_200);
 
-        // Blink LED 3 times quickly
-        for i in 1..=5 {
-            rprintln!("Blink {}/3", i);
-            led.set_low(); // LED on
-            delay_ms(75);
-            led.set_high(); // LED off
-            delay_ms(75);
-        }
+    log!("=== STM32 Loop Speed Test ===");
+    log!("Target counts: {:?}", TARGET_COUNTS);
+    log!("Press button to run the next preset");
 
-        rprintln!("Test complete. Press button to run again.");
+    // Enable DWT cycle counter for precise timing
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
 
-        // Wait for button release before next iteration
-        while button.is_low() {
-            // Wait for button to be released
-        }
+    let mut demo = CounterDemo::new();
+    log!("Ready! Press button to start...");
 
-        delay_ms(200); // Debounce delay
-    }
+    run_on_button(&mut demo, &mut ctx, &mut button)
 }
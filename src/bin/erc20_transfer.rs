@@ -0,0 +1,72 @@
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::entry;
+use panic_rtt_target as _;
+use rtt_target::rtt_init_print;
+use stm32f4xx_hal::{pac, prelude::*};
+
+use stm32_tests::board;
+use stm32_tests::demos::{erc20::Erc20TransferDemo, run_on_button, DemoContext};
+use stm32_tests::log;
+use stm32_tests::utils::button::Button;
+use stm32_tests::utils::delay::TimerDelay;
+use stm32_tests::utils::led::StatusLed;
+use stm32_tests::utils::timing::CycleTimer;
+
+#[entry]
+fn main() -> ! {
+    // Initialize RTT for debug output
+    rtt_init_print!();
+
+    // Initialize heap for RLP encoding
+    const HEAP_SIZE: usize = 8 * 1024;
+    const _: () = assert!(
+        board::heap_fits(HEAP_SIZE),
+        "heap plus stack reserve exceeds board RAM"
+    );
+    static mut HEAP_MEM: [u8; HEAP_SIZE] = [0; HEAP_SIZE];
+    unsafe {
+        stm32_tests::ALLOCATOR.init(
+            core::ptr::addr_of!(HEAP_MEM) as *const _ as usize,
+            HEAP_SIZE,
+        )
+    }
+
+    // Get device peripherals
+    let dp = pac::Peripherals::take().unwrap();
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+
+    // Run the core from the PLL and keep RCC for enabling GPIO clocks
+    let mut rcc = board::init_clocks(dp.RCC);
+
+    // Time demos against the clock just configured
+    let timer = CycleTimer::from_clocks(&rcc.clocks);
+
+    // Setup GPIOC (PC13 LED)
+    let gpioc = dp.GPIOC.split(&mut rcc);
+    let led = StatusLed::new(gpioc.pc13.into_push_pull_output());
+    let mut ctx = DemoContext::new(led, TimerDelay::new(cp.SYST, &rcc.clocks), timer);
+
+    // Setup GPIOA (PA0 Button)
+    let gpioa = dp.GPIOA.split(&mut rcc);
+    let mut button = Button::new(gpioa.pa0.into_pull_up_input());
+
+    // Route log output to USART1 when built with the `uart` feature
+    #[cfg(feature = "uart")]
+    stm32_tests::utils::logger::init_uart(gpioa.pa9, dp.USART1, &mut rcc, 115_200);
+
+    log!("=== STM32 ERC-20 Transfer Signing Demo ===");
+    log!("Builds and signs an EIP-1559 token transfer");
+
+    // Enable DWT cycle counter for precise timing
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    // Load the provisioned key
+    let mut demo = Erc20TransferDemo::new();
+
+    log!("Ready! Press button to sign a transfer...");
+
+    run_on_button(&mut demo, &mut ctx, &mut button)
+}
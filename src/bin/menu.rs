@@ -0,0 +1,150 @@
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::entry;
+use panic_rtt_target as _;
+use rtt_target::{rtt_init_default, set_print_channel};
+use stm32f4xx_hal::{pac, prelude::*};
+
+use stm32_tests::board;
+use stm32_tests::demos::{
+    counter::CounterDemo, ecdsa::EcdsaDemo, eip712::Eip712Demo, erc20::Erc20TransferDemo,
+    falcon::Falcon512Demo, groth16::Groth16Demo, Demo, DemoContext,
+};
+use stm32_tests::log;
+use stm32_tests::transcript;
+use stm32_tests::utils::button::Button;
+use stm32_tests::utils::delay::TimerDelay;
+use stm32_tests::utils::led::StatusLed;
+use stm32_tests::utils::timing::CycleTimer;
+use stm32_tests::utils::transcript;
+
+// Holding the button at least this long runs the selected demo
+const LONG_PRESS_MS: u32 = 1000;
+
+// Button polling interval while measuring a press
+const POLL_MS: u32 = 10;
+
+#[entry]
+fn main() -> ! {
+    // Initialize RTT with a down channel for host commands
+    let channels = rtt_init_default!();
+    set_print_channel(channels.up.0);
+    let mut input = channels.down.0;
+
+    // Initialize heap, sized for the largest demo (Falcon512)
+    const HEAP_SIZE: usize = 96 * 1024;
+    const _: () = assert!(
+        board::heap_fits(HEAP_SIZE),
+        "heap plus stack reserve exceeds board RAM"
+    );
+    static mut HEAP_MEM: [u8; HEAP_SIZE] = [0; HEAP_SIZE];
+    unsafe {
+        stm32_tests::ALLOCATOR.init(
+            core::ptr::addr_of!(HEAP_MEM) as *const _ as usize,
+            HEAP_SIZE,
+        )
+    }
+
+    // Get device peripherals
+    let dp = pac::Peripherals::take().unwrap();
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+
+    // Run the core from the PLL and keep RCC for enabling GPIO clocks
+    let mut rcc = board::init_clocks(dp.RCC);
+
+    // Time demos against the clock just configured
+    let timer = CycleTimer::from_clocks(&rcc.clocks);
+
+    // Setup GPIOC (PC13 LED)
+    let gpioc = dp.GPIOC.split(&mut rcc);
+    let led = StatusLed::new(gpioc.pc13.into_push_pull_output());
+    let mut ctx = DemoContext::new(led, TimerDelay::new(cp.SYST, &rcc.clocks), timer);
+
+    // Setup GPIOA (PA0 Button)
+    let gpioa = dp.GPIOA.split(&mut rcc);
+    let mut button = Button::new(gpioa.pa0.into_pull_up_input());
+
+    // Route log output to USART1 when built with the `uart` feature
+    #[cfg(feature = "uart")]
+    stm32_tests::utils::logger::init_uart(gpioa.pa9, dp.USART1, &mut rcc, 115_200);
+
+    log!("=== STM32 Demo Menu ===");
+    log!("Short press: next demo, long press: run it");
+    log!("Or type 1-6 in the RTT terminal to run a demo directly, d to dump the transcript");
+
+    // Enable DWT cycle counter for precise timing
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut ecdsa = EcdsaDemo;
+    let mut eip712 = Eip712Demo;
+    let mut erc20 = Erc20TransferDemo::new();
+    let mut groth16 = Groth16Demo::new();
+    let mut falcon = Falcon512Demo;
+    let mut counter = CounterDemo::new();
+    let mut demos: [&mut dyn Demo; 6] = [
+        &mut ecdsa,
+        &mut eip712,
+        &mut erc20,
+        &mut groth16,
+        &mut falcon,
+        &mut counter,
+    ];
+
+    let mut selected = 0;
+    print_menu(&demos, selected);
+
+    loop {
+        // Host command: a digit runs that demo directly
+        let mut command = [0u8; 1];
+        if input.read(&mut command) > 0 {
+            if command[0] == b'd' {
+                transcript::dump();
+            } else if let Some(index) = (command[0] as char).to_digit(10) {
+                let index = index as usize;
+                if (1..=demos.len()).contains(&index) {
+                    selected = index - 1;
+                    run_selected(&mut demos, selected, &mut ctx);
+                    print_menu(&demos, selected);
+                }
+            }
+        }
+
+        if !button.is_pressed() {
+            continue;
+        }
+
+        // Measure how long the button is held, then wait out the bounce
+        let mut held_ms = 0;
+        while button.is_pressed() {
+            ctx.delay.delay_ms(POLL_MS);
+            held_ms += POLL_MS;
+        }
+        button.wait_for_release();
+
+        if held_ms >= LONG_PRESS_MS {
+            run_selected(&mut demos, selected, &mut ctx);
+        } else {
+            selected = (selected + 1) % demos.len();
+        }
+        print_menu(&demos, selected);
+    }
+}
+
+/// Print the list of demos, marking the selected one
+fn print_menu(demos: &[&mut dyn Demo], selected: usize) {
+    log!("\n--- Demos ---");
+    for (i, demo) in demos.iter().enumerate() {
+        let marker = if i == selected { '>' } else { ' ' };
+        log!("{} {}. {}", marker, i + 1, demo.name());
+    }
+}
+
+/// Run the selected demo once
+fn run_selected(demos: &mut [&mut dyn Demo], selected: usize, ctx: &mut DemoContext) {
+    log!("\n>>> Running {}", demos[selected].name());
+    transcript!("run {}", demos[selected].name());
+    demos[selected].run(ctx);
+    transcript!("done {}", demos[selected].name());
+}
@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::entry;
+use panic_rtt_target as _;
+use rtt_target::rtt_init_print;
+use stm32f4xx_hal::{pac, prelude::*};
+
+use stm32_tests::board;
+use stm32_tests::log;
+use stm32_tests::utils::delay::TimerDelay;
+use stm32_tests::utils::led::{BlinkPattern, StatusLed};
+use stm32_tests::{DisplaySize, OledDisplay, OledError};
+
+#[entry]
+fn main() -> ! {
+    // Initialize RTT for debug output
+    rtt_init_print!();
+
+    // Get device peripherals
+    let dp = pac::Peripherals::take().unwrap();
+    let cp = cortex_m::Peripherals::take().unwrap();
+
+    // Run the core from the PLL and keep RCC for enabling GPIO and SPI clocks
+    let mut rcc = board::init_clocks(dp.RCC);
+    let mut delay = TimerDelay::new(cp.SYST, &rcc.clocks);
+
+    // Setup GPIOC (PC13 LED)
+    let gpioc = dp.GPIOC.split(&mut rcc);
+    let mut led = StatusLed::new(gpioc.pc13.into_push_pull_output());
+
+    // Display on SPI1 (PA5/PA7) with DC on PB0 and RES on PB1
+    let gpioa = dp.GPIOA.split(&mut rcc);
+    let gpiob = dp.GPIOB.split(&mut rcc);
+
+    // Route log output to USART1 when built with the `uart` feature
+    #[cfg(feature = "uart")]
+    stm32_tests::utils::logger::init_uart(gpioa.pa9, dp.USART1, &mut rcc, 115_200);
+
+    log!("=== STM32 OLED Demo ===");
+
+    let result = OledDisplay::new_default(
+        dp.SPI1,
+        gpioa.pa5,
+        gpioa.pa7,
+        gpiob.pb0,
+        gpiob.pb1,
+        DisplaySize::Display128x64,
+        &mut rcc,
+    )
+    .and_then(|mut display| draw(&mut display));
+
+    match result {
+        Ok(()) => {
+            log!("Display initialized and drawn");
+            BlinkPattern::Success.play(&mut led, &mut delay);
+            loop {
+                cortex_m::asm::wfi();
+            }
+        }
+        Err(e) => {
+            log!("Display error: {:?}", e);
+            loop {
+                BlinkPattern::Error.play(&mut led, &mut delay);
+            }
+        }
+    }
+}
+
+/// Draw the greeting under a status bar and push it to the panel
+fn draw(display: &mut OledDisplay) -> Result<(), OledError> {
+    display.clear()?;
+    display.draw_status_bar("STM32", true)?;
+    display.draw_text("Hello STM32 OLED!", 13, 4)?; // Centered in the frame
+    display.draw_rect(0, 16, 128, 48);
+    display.flush()
+}
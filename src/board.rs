@@ -0,0 +1,48 @@
+//! Board-level setup for the WeAct BlackPill (STM32F411CEU6)
+//!
+//! Out of reset the core runs from the 16 MHz HSI. `init_clocks` switches
+//! to the board's 25 MHz crystal and the PLL so the core runs at
+//! `SYSCLK_HZ`, which the busy-wait `Delay` assumes. Demo timings and
+//! `TimerDelay` instead use the frozen clocks (see `utils::timing`).
+
+use stm32f4xx_hal::{
+    pac::RCC,
+    prelude::*,
+    rcc::{Config, Rcc},
+};
+
+/// External crystal fitted to the BlackPill
+pub const HSE_HZ: u32 = 25_000_000;
+
+/// Core clock configured by `init_clocks`
+pub const SYSCLK_HZ: u32 = 84_000_000;
+
+/// Core cycles per millisecond at `SYSCLK_HZ`
+pub const CYCLES_PER_MS: u32 = SYSCLK_HZ / 1000;
+
+// `RAM_SIZE`, generated by build.rs from the linker script
+include!(concat!(env!("OUT_DIR"), "/memory.rs"));
+
+/// RAM kept free of the heap for the stack and other statics
+pub const STACK_RESERVE: usize = 16 * 1024;
+
+/// Whether a heap of `heap_size` bytes leaves `STACK_RESERVE` of RAM free
+///
+/// Binaries check this at compile time, so a heap sized for the F411 fails
+/// the build once `memory.x` describes a part with less RAM, instead of
+/// corrupting the stack:
+///
+/// ```ignore
+/// const _: () = assert!(board::heap_fits(HEAP_SIZE), "heap too large for board RAM");
+/// ```
+pub const fn heap_fits(heap_size: usize) -> bool {
+    heap_size + STACK_RESERVE <= RAM_SIZE
+}
+
+/// Configure HSE and PLL for `SYSCLK_HZ` and return the frozen RCC
+///
+/// The returned `Rcc` still enables peripheral clocks (e.g. for GPIO
+/// `split`), and its `clocks` field holds the resulting frequencies.
+pub fn init_clocks(rcc: RCC) -> Rcc {
+    rcc.freeze(Config::hse(HSE_HZ.Hz()).sysclk(SYSCLK_HZ.Hz()))
+}
@@ -0,0 +1,66 @@
+//! Loop speed test
+//!
+//! Each run counts to the next target preset and reports the cycle count
+//! and loop rate measured with the DWT cycle counter.
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::utils::bench;
+use crate::utils::led::BlinkPattern;
+
+/// Target counts - each run uses the next preset
+pub const TARGET_COUNTS: [u64; 3] = [1_000_000, 10_000_000, 100_000_000];
+
+/// Counting loop benchmark cycling through `TARGET_COUNTS`
+#[derive(Default)]
+pub struct CounterDemo {
+    preset: usize,
+}
+
+impl CounterDemo {
+    /// Create the demo, starting at the first preset
+    pub fn new() -> Self {
+        CounterDemo { preset: 0 }
+    }
+}
+
+impl Demo for CounterDemo {
+    fn name(&self) -> &'static str {
+        "Loop counter"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        let target = TARGET_COUNTS[self.preset];
+        self.preset = (self.preset + 1) % TARGET_COUNTS.len();
+        log!("Button pressed! Counting to {}...", target);
+
+        let start_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Start counting loop - this is the speed test
+        // Use volatile operations to prevent compiler optimization
+        let mut count: u64 = 0;
+        while count < target {
+            count += 1;
+            // Use black_box to prevent the compiler from optimizing away the loop
+            core::hint::black_box(&count);
+        }
+
+        let cycles = cortex_m::peripheral::DWT::cycle_count().wrapping_sub(start_cycles) as u64;
+
+        log!("Count reached {}! Blinking LED...", count);
+        log!("  Cycles: {}", cycles);
+        bench::report(&ctx.timer, "counter_loop", cycles as u32);
+        if let Some(rate) = (count * ctx.timer.sysclk_hz() as u64).checked_div(cycles) {
+            log!("  Rate: {} counts/s", rate);
+            log!(
+                "  Cycles per count: {}.{:02}",
+                cycles / count,
+                cycles * 100 / count % 100
+            );
+        }
+
+        BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+
+        log!("Test complete.");
+    }
+}
@@ -0,0 +1,143 @@
+//! Ethereum ECDSA key generation, signing and verification (secp256k1)
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::transcript;
+use crate::utils::bench;
+use crate::utils::crypto::{
+    bytes_to_hex_string, keccak256, to_checksum_address, DemoRng, EthereumKeyPair,
+    InsecureCryptoRng,
+};
+use crate::utils::led::BlinkPattern;
+
+/// Generate a key pair, sign a message and verify the signature
+pub struct EcdsaDemo;
+
+impl Demo for EcdsaDemo {
+    fn name(&self) -> &'static str {
+        "ECDSA (secp256k1)"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        log!("\n=== Starting ECDSA Demo ===");
+
+        // Get start time (using DWT cycle counter for precise timing)
+        let start_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("Start cycles: {}", start_cycles);
+
+        // Step 1: Generate Ethereum key pair
+        log!("\n[1/5] Generating Ethereum key pair (secp256k1)...");
+        // Use a combination of cycle counter and a changing value for seed
+        let seed = start_cycles.wrapping_add(cortex_m::peripheral::DWT::cycle_count());
+        log!("RNG seed: 0x{:08X}", seed);
+
+        let mut rng = InsecureCryptoRng::new(DemoRng::new(seed));
+        let keypair = EthereumKeyPair::generate(&mut rng);
+
+        log!("✓ Ethereum key pair generated successfully");
+
+        // Print private key (32 bytes = 64 hex chars)
+        let private_key_bytes = keypair.private_key_bytes();
+        let mut priv_hex_buffer = [0u8; 64];
+        let priv_hex = bytes_to_hex_string(&private_key_bytes, &mut priv_hex_buffer);
+        log!("  Private Key: 0x{}", priv_hex);
+
+        // Print public key (compressed - 33 bytes = 66 hex chars)
+        let pub_bytes = keypair.public_key_compressed_bytes();
+        let mut pub_hex_buffer = [0u8; 66];
+        let pub_hex = bytes_to_hex_string(&pub_bytes, &mut pub_hex_buffer);
+        log!("  Public Key (compressed): 0x{}", pub_hex);
+
+        // Print Ethereum address (20 bytes = 40 hex chars)
+        let mut addr_hex_buffer = [0u8; 42];
+        let addr_hex = to_checksum_address(&keypair.address, &mut addr_hex_buffer);
+        log!("  Ethereum Address: {}", addr_hex);
+
+        let step1_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step1_cycles.wrapping_sub(start_cycles));
+        bench::report(
+            &ctx.timer,
+            "ecdsa_keygen",
+            step1_cycles.wrapping_sub(start_cycles),
+        );
+
+        // Step 2: Create message and hash it
+        log!("\n[2/5] Creating message to sign...");
+        let message = b"Hello, Ethereum ECDSA on STM32!";
+        log!("  Message: {:?}", core::str::from_utf8(message).unwrap());
+
+        // Hash the message using Keccak256 (Ethereum standard)
+        let message_hash = keccak256(message);
+        log!(
+            "  Keccak256 hash: [{:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}]...",
+            message_hash[0],
+            message_hash[1],
+            message_hash[2],
+            message_hash[3],
+            message_hash[4],
+            message_hash[5],
+            message_hash[6],
+            message_hash[7]
+        );
+
+        let step2_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step2_cycles.wrapping_sub(step1_cycles));
+
+        // Step 3: Sign the message
+        log!("\n[3/5] Signing message with ECDSA...");
+        let signature = keypair.sign(message);
+        log!("✓ Signature generated successfully");
+        let sig_bytes = signature.to_bytes();
+        log!("  Signature (r,s): [{:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}, {:02X}]...",
+                  sig_bytes[0], sig_bytes[1], sig_bytes[2], sig_bytes[3],
+                  sig_bytes[4], sig_bytes[5], sig_bytes[6], sig_bytes[7]);
+
+        let step3_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step3_cycles.wrapping_sub(step2_cycles));
+        bench::report(
+            &ctx.timer,
+            "ecdsa_sign",
+            step3_cycles.wrapping_sub(step2_cycles),
+        );
+
+        // Step 4: Blink LED to show signature was generated
+        log!("\n[4/5] Blinking LED (signature generated)...");
+        BlinkPattern::Working.play(&mut ctx.led, &mut ctx.delay);
+
+        let step4_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Step 5: Verify the signature
+        log!("\n[5/5] Verifying signature...");
+        match keypair.verify(message, &signature) {
+            Ok(_) => {
+                log!("✓ Signature verification SUCCESSFUL!");
+
+                let step5_cycles = cortex_m::peripheral::DWT::cycle_count();
+                log!("  Cycles: {}", step5_cycles.wrapping_sub(step4_cycles));
+                bench::report(
+                    &ctx.timer,
+                    "ecdsa_verify",
+                    step5_cycles.wrapping_sub(step4_cycles),
+                );
+
+                BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+
+                // Calculate total time
+                let end_cycles = cortex_m::peripheral::DWT::cycle_count();
+                let total_cycles = end_cycles.wrapping_sub(start_cycles);
+                log!("\n=== Demo Complete ===");
+                log!("Total cycles: {}", total_cycles);
+                bench::report(&ctx.timer, "ecdsa_total", total_cycles);
+                log!(
+                    "Approximate time: ~{} ms",
+                    ctx.timer.cycles_to_ms(total_cycles)
+                );
+            }
+            Err(e) => {
+                transcript!("ecdsa: verify failed: {:?}", e);
+                log!("✗ Signature verification FAILED!");
+                BlinkPattern::Failure.play(&mut ctx.led, &mut ctx.delay);
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! EIP-712 typed data signing for a multisig transaction
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::transcript;
+use crate::utils::bench;
+use crate::utils::crypto::{
+    bytes_to_hex_string, eip712_hash, sign_eip712, to_checksum_address, verify_eip712, DemoRng,
+    Eip712Domain, Eip712Struct, EthereumKeyPair, InsecureCryptoRng, MultisigTransaction,
+};
+use crate::utils::led::BlinkPattern;
+use crate::utils::units::{format_wei_as_ether, ETHER_DECIMALS, MAX_FORMATTED_LEN};
+
+// Ethereum mainnet chain id
+const CHAIN_ID: u64 = 1;
+
+/// Sign and verify EIP-712 typed data with a fresh key pair
+pub struct Eip712Demo;
+
+impl Demo for Eip712Demo {
+    fn name(&self) -> &'static str {
+        "EIP-712 typed data"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        log!("\n=== Starting EIP712 Demo ===");
+
+        // Get start time (using DWT cycle counter for precise timing)
+        let start_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("Start cycles: {}", start_cycles);
+
+        // Step 1: Generate Ethereum key pair
+        log!("\n[1/5] Generating Ethereum key pair (secp256k1)...");
+        let seed = start_cycles.wrapping_add(cortex_m::peripheral::DWT::cycle_count());
+        log!("RNG seed: 0x{:08X}", seed);
+
+        let mut rng = InsecureCryptoRng::new(DemoRng::new(seed));
+        let keypair = EthereumKeyPair::generate(&mut rng);
+
+        log!("✓ Ethereum key pair generated successfully");
+
+        // Print private key (32 bytes = 64 hex chars)
+        let private_key_bytes = keypair.private_key_bytes();
+        let mut priv_hex_buffer = [0u8; 64];
+        let priv_hex = bytes_to_hex_string(&private_key_bytes, &mut priv_hex_buffer);
+        log!("  Private Key: 0x{}", priv_hex);
+
+        // Print Ethereum address (20 bytes = 40 hex chars)
+        let mut addr_hex_buffer = [0u8; 42];
+        let addr_hex = to_checksum_address(&keypair.address, &mut addr_hex_buffer);
+        log!("  Ethereum Address: {}", addr_hex);
+
+        let step1_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step1_cycles.wrapping_sub(start_cycles));
+        bench::report(
+            &ctx.timer,
+            "eip712_keygen",
+            step1_cycles.wrapping_sub(start_cycles),
+        );
+
+        // Step 2: Create EIP712 domain and typed data
+        log!("\n[2/5] Creating EIP712 typed data (Multisig Transaction)...");
+
+        // Create EIP712 domain
+        let verifying_contract = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+            0xde, 0xf0, 0x12, 0x34, 0x56, 0x78,
+        ];
+        let domain =
+            Eip712Domain::with_chain_id_u64("MyMultisig", "1", CHAIN_ID, verifying_contract);
+
+        log!("  Domain:");
+        log!("    Name: {}", domain.name);
+        log!("    Version: {}", domain.version);
+        log!("    Chain ID: {}", CHAIN_ID);
+        let mut contract_hex_buffer = [0u8; 40];
+        let contract_hex = bytes_to_hex_string(&verifying_contract, &mut contract_hex_buffer);
+        log!("    Verifying Contract: 0x{}", contract_hex);
+
+        // Create multisig transaction
+        let to_address = [
+            0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+        ];
+        let tx_data = b"transfer(address,uint256)";
+        let transaction = MultisigTransaction::new(to_address, 1000000000000000000, tx_data, 42);
+
+        log!("  Transaction:");
+        let mut to_hex_buffer = [0u8; 40];
+        let to_hex = bytes_to_hex_string(&transaction.to, &mut to_hex_buffer);
+        log!("    To: 0x{}", to_hex);
+        let mut value_word = [0u8; 32];
+        value_word[24..].copy_from_slice(&transaction.value.to_be_bytes());
+        let mut value_buffer = [0u8; MAX_FORMATTED_LEN];
+        let value_eth = format_wei_as_ether(&value_word, ETHER_DECIMALS, &mut value_buffer);
+        log!("    Value: {} ETH ({} wei)", value_eth, transaction.value);
+        log!(
+            "    Data: {:?}",
+            core::str::from_utf8(transaction.data).unwrap()
+        );
+        log!("    Nonce: {}", transaction.nonce);
+
+        // Compute struct hash
+        let struct_hash = transaction.hash_struct();
+        let mut struct_hash_buffer = [0u8; 64];
+        let struct_hash_hex = bytes_to_hex_string(&struct_hash, &mut struct_hash_buffer);
+        log!("  Struct Hash: 0x{}", struct_hash_hex);
+
+        // Compute EIP712 message hash
+        let message_hash = eip712_hash(&domain, &struct_hash);
+        let mut msg_hash_buffer = [0u8; 64];
+        let msg_hash_hex = bytes_to_hex_string(&message_hash, &mut msg_hash_buffer);
+        log!("  EIP712 Message Hash: 0x{}", msg_hash_hex);
+
+        let step2_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step2_cycles.wrapping_sub(step1_cycles));
+
+        // Step 3: Sign the typed data
+        log!("\n[3/5] Signing EIP712 typed data with ECDSA...");
+        let signature = sign_eip712(&keypair, &domain, &transaction);
+        log!("✓ Signature generated successfully");
+        let sig_bytes = signature.to_bytes();
+        let mut sig_hex_buffer = [0u8; 128];
+        let sig_hex = bytes_to_hex_string(&sig_bytes, &mut sig_hex_buffer);
+        log!("  Signature: 0x{}", sig_hex);
+
+        let step3_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step3_cycles.wrapping_sub(step2_cycles));
+        bench::report(
+            &ctx.timer,
+            "eip712_sign",
+            step3_cycles.wrapping_sub(step2_cycles),
+        );
+
+        // Step 4: Blink LED to show signature was generated
+        log!("\n[4/5] Blinking LED (signature generated)...");
+        BlinkPattern::Working.play(&mut ctx.led, &mut ctx.delay);
+
+        let step4_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Step 5: Verify the signature
+        log!("\n[5/5] Verifying EIP712 signature...");
+        match verify_eip712(&keypair, &domain, &transaction, &signature) {
+            Ok(_) => {
+                log!("✓ EIP712 Signature verification SUCCESSFUL!");
+
+                let step5_cycles = cortex_m::peripheral::DWT::cycle_count();
+                log!("  Cycles: {}", step5_cycles.wrapping_sub(step4_cycles));
+                bench::report(
+                    &ctx.timer,
+                    "eip712_verify",
+                    step5_cycles.wrapping_sub(step4_cycles),
+                );
+
+                BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+
+                // Calculate total time
+                let end_cycles = cortex_m::peripheral::DWT::cycle_count();
+                let total_cycles = end_cycles.wrapping_sub(start_cycles);
+                log!("\n=== Demo Complete ===");
+                log!("Total cycles: {}", total_cycles);
+                bench::report(&ctx.timer, "eip712_total", total_cycles);
+                log!(
+                    "Approximate time: ~{} ms",
+                    ctx.timer.cycles_to_ms(total_cycles)
+                );
+            }
+            Err(e) => {
+                transcript!("eip712: verify failed: {:?}", e);
+                log!("✗ EIP712 Signature verification FAILED!");
+                BlinkPattern::Failure.play(&mut ctx.led, &mut ctx.delay);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! ERC-20 transfer signing as an EIP-1559 transaction
+//!
+//! Needs a small heap (a few KiB) for the RLP encoding.
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::transcript;
+use crate::utils::abi::encode_transfer;
+use crate::utils::crypto::{bytes_to_hex_string, to_checksum_address, EthereumKeyPair};
+use crate::utils::led::BlinkPattern;
+use crate::utils::tx::Eip1559Transaction;
+use crate::utils::units::{format_wei_as_ether, MAX_FORMATTED_LEN};
+
+// Provisioned signing key. This is the well-known Hardhat/Anvil test
+// account #0 - never send real funds to it.
+const PRIVATE_KEY: [u8; 32] = [
+    0xac, 0x09, 0x74, 0xbe, 0xc3, 0x9a, 0x17, 0xe3, 0x6b, 0xa4, 0xa6, 0xb4, 0xd2, 0x38, 0xff, 0x94,
+    0x4b, 0xac, 0xb4, 0x78, 0xcb, 0xed, 0x5e, 0xfc, 0xae, 0x78, 0x4d, 0x7b, 0xf4, 0xf2, 0xff, 0x80,
+];
+
+// Sepolia chain id
+const CHAIN_ID: u64 = 11_155_111;
+
+// USDC token contract on Sepolia
+const TOKEN_CONTRACT: [u8; 20] = [
+    0x1c, 0x7d, 0x4b, 0x19, 0x6c, 0xb0, 0xc7, 0xb0, 0x1d, 0x74, 0x3f, 0xbc, 0x61, 0x16, 0xa9, 0x02,
+    0x37, 0x9c, 0x72, 0x38,
+];
+
+// USDC uses 6 decimals
+const TOKEN_DECIMALS: u8 = 6;
+
+/// Build and sign an ERC-20 transfer with a provisioned key
+pub struct Erc20TransferDemo {
+    keypair: EthereumKeyPair,
+}
+
+impl Erc20TransferDemo {
+    /// Load the provisioned key and print the signer address
+    pub fn new() -> Self {
+        let keypair = EthereumKeyPair::from_private_key_bytes(&PRIVATE_KEY).unwrap();
+
+        let mut addr_hex_buffer = [0u8; 42];
+        let addr_hex = to_checksum_address(&keypair.address, &mut addr_hex_buffer);
+        log!("Signer address: {}", addr_hex);
+
+        Erc20TransferDemo { keypair }
+    }
+}
+
+impl Default for Erc20TransferDemo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Demo for Erc20TransferDemo {
+    fn name(&self) -> &'static str {
+        "ERC-20 transfer"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        let keypair = &self.keypair;
+
+        log!("\n=== Signing ERC-20 Transfer ===");
+
+        // Step 1: ABI-encode transfer(address,uint256)
+        log!("\n[1/3] Encoding transfer calldata...");
+        let recipient = [
+            0x70, 0x99, 0x79, 0x70, 0xc5, 0x18, 0x12, 0xdc, 0x3a, 0x01, 0x0c, 0x7d, 0x01, 0xb5,
+            0x0e, 0x0d, 0x17, 0xdc, 0x79, 0xc8,
+        ];
+        // 1 USDC (6 decimals) as a 32-byte big-endian integer
+        let mut amount = [0u8; 32];
+        amount[24..].copy_from_slice(&1_000_000u64.to_be_bytes());
+        let calldata = encode_transfer(&recipient, &amount);
+
+        let mut amount_buffer = [0u8; MAX_FORMATTED_LEN];
+        let amount_usdc = format_wei_as_ether(&amount, TOKEN_DECIMALS, &mut amount_buffer);
+        log!("  Amount: {} USDC", amount_usdc);
+
+        let mut calldata_hex_buffer = [0u8; 136];
+        let calldata_hex = bytes_to_hex_string(&calldata, &mut calldata_hex_buffer);
+        log!("  Calldata: 0x{}", calldata_hex);
+
+        // Step 2: Build and sign the EIP-1559 transaction
+        log!("\n[2/3] Signing EIP-1559 transaction...");
+        let tx = Eip1559Transaction {
+            chain_id: CHAIN_ID,
+            nonce: 0,
+            max_priority_fee_per_gas: 1_500_000_000, // 1.5 gwei
+            max_fee_per_gas: 30_000_000_000,         // 30 gwei
+            gas_limit: 65_000,
+            to: TOKEN_CONTRACT,
+            value: 0,
+            data: &calldata,
+        };
+
+        match tx.sign(keypair) {
+            Ok(raw_tx) => {
+                log!("✓ Transaction signed ({} bytes)", raw_tx.len());
+
+                // Step 3: Print the broadcastable raw transaction
+                log!("\n[3/3] Raw transaction (eth_sendRawTransaction):");
+                let mut raw_hex_buffer = [0u8; 512];
+                let raw_hex = bytes_to_hex_string(&raw_tx, &mut raw_hex_buffer);
+                log!("0x{}", raw_hex);
+
+                BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+            }
+            Err(e) => {
+                transcript!("erc20: signing failed: {:?}", e);
+                log!("✗ Signing FAILED!");
+                BlinkPattern::Error.play(&mut ctx.led, &mut ctx.delay);
+            }
+        }
+    }
+}
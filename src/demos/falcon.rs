@@ -0,0 +1,167 @@
+//! Falcon512 post-quantum key generation, signing and verification
+//!
+//! Needs a large heap (96 KiB) for miden-crypto.
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::transcript;
+use crate::utils::bench;
+use crate::utils::crypto::DemoRng;
+use crate::utils::falcon::{
+    from_public_key_bytes, signature_from_bytes, signature_to_bytes, verify_with_word,
+    Falcon512KeyPair,
+};
+use crate::utils::led::BlinkPattern;
+
+/// Generate a Falcon512 key pair, sign a message and verify it
+pub struct Falcon512Demo;
+
+impl Demo for Falcon512Demo {
+    fn name(&self) -> &'static str {
+        "Falcon512"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        // IMMEDIATE feedback to confirm button press and RTT working
+        log!("\n=== BUTTON PRESSED ===");
+        log!("Starting Falcon512 Demo...");
+
+        // Get start time (using DWT cycle counter for precise timing)
+        let start_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("Start cycles: {}", start_cycles);
+
+        // Step 1: Generate Falcon512 key pair
+        log!("\n[1/6] Generating Falcon512 key pair...");
+        log!("WARNING: This may take 1-5 minutes on STM32F411 @ 84MHz");
+
+        // Blink LED to show we're starting key generation
+        BlinkPattern::Working.play(&mut ctx.led, &mut ctx.delay);
+
+        log!("Starting key generation NOW...");
+
+        // Create simple RNG using DWT cycle counter
+        let seed_value = start_cycles.wrapping_add(cortex_m::peripheral::DWT::cycle_count());
+        log!("RNG seed: 0x{:08X}", seed_value);
+
+        let mut rng = DemoRng::new(seed_value);
+        let led = &mut ctx.led;
+        let keypair = Falcon512KeyPair::generate_with_progress(&mut rng, |stage| {
+            log!("  Stage: {:?}", stage);
+            led.toggle(); // Flip at each milestone
+        });
+        ctx.led.off();
+
+        log!("✓ Falcon512 key pair generated successfully");
+
+        // Print public key as Word (4 field elements)
+        let pub_word = keypair.public_key_word();
+        log!(
+            "  Public Key (as Word): [{:?}, {:?}, {:?}, {:?}]",
+            pub_word[0],
+            pub_word[1],
+            pub_word[2],
+            pub_word[3]
+        );
+
+        let step1_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step1_cycles.wrapping_sub(start_cycles));
+        bench::report(
+            &ctx.timer,
+            "falcon512_keygen",
+            step1_cycles.wrapping_sub(start_cycles),
+        );
+
+        // Step 2: Blink LED when ready
+        log!("\n[2/6] Ready - blinking LED...");
+        BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+
+        let step2_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Step 3: Create message to sign
+        log!("\n[3/6] Creating message to sign...");
+        let message = b"Hello, Falcon512 Post-Quantum Crypto on STM32!";
+        log!("  Message: {:?}", core::str::from_utf8(message).unwrap());
+        log!("  Message length: {} bytes", message.len());
+
+        let step3_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step3_cycles.wrapping_sub(step2_cycles));
+
+        // Step 4: Sign the message (LED on during signing)
+        log!("\n[4/6] Signing message with Falcon512...");
+        ctx.led.on(); // LED on during signing
+
+        let signature = keypair.sign(message, &mut rng);
+
+        ctx.led.off(); // LED off after signing
+        log!("✓ Signature generated successfully");
+        let sig_bytes = signature_to_bytes(&signature);
+        log!("  Signature: {} bytes", sig_bytes.len());
+
+        let step4_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step4_cycles.wrapping_sub(step3_cycles));
+        bench::report(
+            &ctx.timer,
+            "falcon512_sign",
+            step4_cycles.wrapping_sub(step3_cycles),
+        );
+
+        // Step 5: Blink LED to show signing complete
+        log!("\n[5/6] Signing complete - blinking LED...");
+        BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+
+        let step5_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Step 6: Verify the signature (LED on during verification)
+        log!("\n[6/6] Verifying signature...");
+        ctx.led.on(); // LED on during verification
+
+        // Verify from the serialized key and signature alone, as a second device would
+        let is_valid = match (
+            from_public_key_bytes(&keypair.public_key_bytes()),
+            signature_from_bytes(&sig_bytes),
+        ) {
+            (Ok(public_key), Ok(signature)) => {
+                verify_with_word(public_key.into(), message, &signature)
+            }
+            _ => false,
+        };
+
+        if is_valid {
+            log!("✓ Signature verification SUCCESSFUL!");
+
+            let step6_cycles = cortex_m::peripheral::DWT::cycle_count();
+            log!("  Cycles: {}", step6_cycles.wrapping_sub(step5_cycles));
+            bench::report(
+                &ctx.timer,
+                "falcon512_verify",
+                step6_cycles.wrapping_sub(step5_cycles),
+            );
+
+            // Keep LED ON to indicate successful signature verification
+            log!("\nKeeping LED ON (signature verified successfully)...");
+            ctx.led.on();
+
+            // Calculate total time
+            let end_cycles = cortex_m::peripheral::DWT::cycle_count();
+            let total_cycles = end_cycles.wrapping_sub(start_cycles);
+            log!("\n=== Demo Complete ===");
+            log!("Total cycles: {}", total_cycles);
+            bench::report(&ctx.timer, "falcon512_total", total_cycles);
+            log!(
+                "Approximate time: ~{} ms",
+                ctx.timer.cycles_to_ms(total_cycles)
+            );
+
+            log!("\n=== Falcon512 Security Info ===");
+            log!("Security Level: NIST Level 1 (128-bit quantum security)");
+            log!("Public Key: Word (4 field elements)");
+            log!("Signature: Falcon512 format");
+            log!("Post-Quantum: Resistant to quantum computer attacks");
+            log!("Algorithm: Lattice-based (NTRU lattices)");
+        } else {
+            transcript!("falcon512: verify failed");
+            log!("✗ Signature verification FAILED!");
+            BlinkPattern::Failure.play(&mut ctx.led, &mut ctx.delay);
+        }
+    }
+}
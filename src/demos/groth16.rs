@@ -0,0 +1,246 @@
+//! Groth16 proof verification on BN254 (IMT preimage proof)
+//!
+//! Needs a heap (64 KiB) for arkworks.
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+
+use super::{Demo, DemoContext};
+use crate::log;
+use crate::transcript;
+use crate::utils::bench;
+use crate::utils::crypto::bytes_to_hex_string;
+use crate::utils::groth16::{self, vk_proof, PreparedVk};
+use crate::utils::led::BlinkPattern;
+use crate::utils::rng::EntropyPool;
+use crate::utils::stack;
+use crate::utils::timing::CycleTimer;
+
+/// Number of proofs in the batch verification comparison
+const BATCH_SIZE: usize = 4;
+
+/// Verify the bundled sample proof
+///
+/// The verification key is prepared once, on `prepare()` or the first run.
+#[derive(Default)]
+pub struct Groth16Demo {
+    pvk: Option<PreparedVk>,
+}
+
+impl Groth16Demo {
+    /// Create the demo without preparing the verification key yet
+    pub fn new() -> Self {
+        Groth16Demo { pvk: None }
+    }
+
+    /// Prepare and validate the verification key (expensive, done once)
+    pub fn prepare(&mut self) -> &PreparedVk {
+        self.pvk.get_or_insert_with(|| {
+            // Load and prepare verification key once (expensive operation)
+            log!("\nPreparing verification key...");
+            let vk_data = vk_proof::verification_key();
+
+            // Convert to groth16::Vk
+            let vk = groth16::Vk {
+                alpha_g1: vk_data.alpha_g1,
+                beta_g2: vk_data.beta_g2,
+                gamma_g2: vk_data.gamma_g2,
+                delta_g2: vk_data.delta_g2,
+                ic: &vk_data.ic,
+            };
+
+            let pvk = vk.prepare();
+            log!("✓ Verification key prepared");
+
+            // Catch a corrupted key up front rather than as a failed verification
+            if let Err(e) = pvk.validate() {
+                transcript!("groth16: invalid vk: {:?}", e);
+                log!("✗ Verification key is invalid: {:?}", e);
+            } else {
+                log!("✓ Verification key validated");
+            }
+
+            let mut digest_hex_buffer = [0u8; 64];
+            let digest_hex = bytes_to_hex_string(&pvk.digest(), &mut digest_hex_buffer);
+            log!("  vk_digest: 0x{}", digest_hex);
+
+            pvk
+        })
+    }
+
+    /// Time `BATCH_SIZE` separate verifications against one batched check
+    fn compare_batch(
+        timer: &CycleTimer,
+        pvk: &PreparedVk,
+        proof: &groth16::Proof,
+        public_inputs: &[Fr],
+    ) {
+        log!(
+            "\n=== Batch Verification ({} copies of the proof) ===",
+            BATCH_SIZE
+        );
+
+        let single_start = timer.start();
+        let all_valid = (0..BATCH_SIZE)
+            .all(|_| groth16::verify_proof_prepared(pvk, proof, public_inputs).is_ok());
+        let single_cycles = timer.elapsed_cycles(single_start);
+        bench::report(timer, "groth16_verify_x4", single_cycles);
+        log!(
+            "  One by one: {} cycles (~{} ms), all valid: {}",
+            single_cycles,
+            timer.cycles_to_ms(single_cycles),
+            all_valid
+        );
+
+        // The batch is only sound if the prover cannot predict the scalars.
+        // This needs a registered source; `insecure-dwt-rng` lets getrandom
+        // fall back to the cycle counter instead (demo only).
+        let mut seed = [0u8; 32];
+        if let Err(err) = getrandom::getrandom(&mut seed) {
+            log!("  Batched: skipped, no entropy source ({})", err);
+            return;
+        }
+        let mut pool = EntropyPool::new();
+        pool.stir(&seed);
+        seed.fill(0);
+
+        let proofs = [*proof; BATCH_SIZE];
+        let inputs = [public_inputs; BATCH_SIZE];
+
+        let batch_start = timer.start();
+        let batch_result = groth16::verify_proofs_batch(pvk, &proofs, &inputs, &mut pool);
+        let batch_cycles = timer.elapsed_cycles(batch_start);
+        bench::report(timer, "groth16_batch_x4", batch_cycles);
+
+        log!(
+            "  Batched:    {} cycles (~{} ms), result: {:?}",
+            batch_cycles,
+            timer.cycles_to_ms(batch_cycles),
+            batch_result
+        );
+        let speedup_x100 = single_cycles as u64 * 100 / batch_cycles.max(1) as u64;
+        log!(
+            "  Speedup: {}.{:02}x",
+            speedup_x100 / 100,
+            speedup_x100 % 100
+        );
+    }
+}
+
+impl Demo for Groth16Demo {
+    fn name(&self) -> &'static str {
+        "Groth16 verifier"
+    }
+
+    fn run(&mut self, ctx: &mut DemoContext) {
+        let pvk = self.prepare();
+
+        log!("\n=== Starting Groth16 Verification ===");
+
+        // Get start time
+        let start_cycles = cortex_m::peripheral::DWT::cycle_count();
+
+        // Step 1: Turn LED on to indicate verification started
+        ctx.led.on();
+        log!("[1/3] Loading proof and public inputs...");
+
+        // Load the sample proof and public inputs
+        let (proof_data, public_inputs) = vk_proof::sample_proof();
+
+        // Convert to groth16::Proof
+        let proof = groth16::Proof {
+            a: proof_data.a,
+            b: proof_data.b,
+            c: proof_data.c,
+        };
+
+        log!("  Proof loaded:");
+        log!("    - Proof.a (G1 point)");
+        log!("    - Proof.b (G2 point)");
+        log!("    - Proof.c (G1 point)");
+        log!("  Public inputs: {} field elements", public_inputs.len());
+
+        // The public input must be the root for the known preimage, otherwise
+        // we would only be verifying whatever the converter produced
+        let expected = groth16::expected_public_input();
+        if public_inputs.first() != Some(&expected) {
+            transcript!("groth16: public input mismatch");
+            log!("✗ Public input does not match the IMT preimage claim!");
+            let mut root_hex_buffer = [0u8; 64];
+            let root_hex =
+                bytes_to_hex_string(&expected.into_repr().to_bytes_be(), &mut root_hex_buffer);
+            log!("  Expected root: 0x{}", root_hex);
+            log!("  public.json is out of step with circom/input.json; re-run the converter");
+            ctx.led.off();
+            BlinkPattern::Failure.play(&mut ctx.led, &mut ctx.delay);
+            return;
+        }
+        log!(
+            "  Public input matches root for preimage x = {}",
+            groth16::SAMPLE_PREIMAGE
+        );
+
+        let step1_cycles = cortex_m::peripheral::DWT::cycle_count();
+        log!("  Cycles: {}", step1_cycles.wrapping_sub(start_cycles));
+
+        // Step 2: Verify the proof
+        log!("\n[2/3] Verifying Groth16 proof...");
+        log!("  This involves pairing checks on BN254 curve");
+        log!("  Please wait (this may take several seconds)...");
+
+        // Paint the free stack so peak usage can be measured afterwards
+        stack::paint();
+
+        let verify_start = cortex_m::peripheral::DWT::cycle_count();
+
+        let result = groth16::verify_proof_prepared(pvk, &proof, &public_inputs);
+
+        let verify_end = cortex_m::peripheral::DWT::cycle_count();
+        let verify_cycles = verify_end.wrapping_sub(verify_start);
+        bench::report(&ctx.timer, "groth16_verify", verify_cycles);
+        log!(
+            "  Peak stack: {} / {} bytes",
+            stack::high_water_mark(),
+            stack::stack_size()
+        );
+
+        match result {
+            Ok(_) => {
+                log!("✓ Proof verification SUCCESSFUL!");
+                log!("  Verification cycles: {}", verify_cycles);
+                log!(
+                    "  Approximate time: ~{} ms",
+                    ctx.timer.cycles_to_ms(verify_cycles)
+                );
+
+                // Step 3: Blink LED to indicate success
+                log!("\n[3/3] Verification successful...");
+                BlinkPattern::Success.play(&mut ctx.led, &mut ctx.delay);
+            }
+            Err(e) => {
+                transcript!("groth16: proof rejected: {:?}", e);
+                log!("✗ Proof verification FAILED: {:?}", e);
+                log!("  Verification cycles: {}", verify_cycles);
+
+                // Step 3: Blink LED to indicate failure
+                log!("\n[3/3] Blinking LED (verification failed)...");
+                BlinkPattern::Failure.play(&mut ctx.led, &mut ctx.delay);
+            }
+        }
+
+        // Calculate total time
+        let end_cycles = cortex_m::peripheral::DWT::cycle_count();
+        let total_cycles = end_cycles.wrapping_sub(start_cycles);
+        log!("\n=== Verification Complete ===");
+        log!("Total cycles: {}", total_cycles);
+        bench::report(&ctx.timer, "groth16_total", total_cycles);
+        log!(
+            "Approximate total time: ~{} ms",
+            ctx.timer.cycles_to_ms(total_cycles)
+        );
+
+        if result.is_ok() {
+            Self::compare_batch(&ctx.timer, pvk, &proof, &public_inputs);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Demo registry
+//!
+//! Each demo's core logic lives here behind the `Demo` trait, so the same
+//! code runs from its dedicated binary in `src/bin/` or from the `menu`
+//! binary, which picks a demo at runtime without reflashing.
+//!
+//! Demos expect the DWT cycle counter to be enabled for timing, and the
+//! heap-using ones (ERC-20, Groth16, Falcon512) expect `crate::ALLOCATOR`
+//! to be initialized.
+
+pub mod counter;
+pub mod ecdsa;
+pub mod eip712;
+pub mod erc20;
+pub mod falcon;
+pub mod groth16;
+
+use stm32f4xx_hal::gpio::{Input, Pin};
+
+use crate::log;
+use crate::transcript;
+use crate::utils::{button::Button, delay::TimerDelay, led::StatusLed, timing::CycleTimer};
+
+// Type alias for the user button (active low with pull-up)
+pub type ButtonPin = Pin<'A', 0, Input>;
+
+/// Hardware shared with a running demo
+pub struct DemoContext {
+    pub led: StatusLed,
+    pub delay: TimerDelay,
+    pub timer: CycleTimer,
+}
+
+impl DemoContext {
+    /// Bundle the status LED, delay provider and cycle timer
+    pub fn new(led: StatusLed, delay: TimerDelay, timer: CycleTimer) -> Self {
+        DemoContext { led, delay, timer }
+    }
+}
+
+/// A demo that can be run repeatedly
+pub trait Demo {
+    /// Short name shown in menus
+    fn name(&self) -> &'static str;
+
+    /// Run the demo once
+    fn run(&mut self, ctx: &mut DemoContext);
+}
+
+/// Run a demo on every button press, forever
+pub fn run_on_button(
+    demo: &mut dyn Demo,
+    ctx: &mut DemoContext,
+    button: &mut Button<ButtonPin>,
+) -> ! {
+    loop {
+        button.wait_for_press();
+
+        transcript!("run {}", demo.name());
+        demo.run(ctx);
+        transcript!("done {}", demo.name());
+
+        log!("\nPress button to run {} again...", demo.name());
+        button.wait_for_release();
+    }
+}
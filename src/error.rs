@@ -0,0 +1,84 @@
+//! Crate-level error type
+//!
+//! Wraps the per-module errors so code that touches several modules can use
+//! `?` throughout and return a single `Result<_, stm32_tests::Error>`.
+
+use core::fmt;
+
+use crate::utils::{crypto::HexError, framing::FramingError, groth16::Groth16Error};
+#[cfg(feature = "stm32f4")]
+use crate::utils::{oled::OledError, secure_link::LinkError};
+
+/// Any error reported by this crate
+#[derive(Debug)]
+pub enum Error {
+    /// OLED display driver error
+    #[cfg(feature = "stm32f4")]
+    Oled(OledError),
+    /// ECDSA key, signing or verification error
+    Ecdsa(ecdsa::Error),
+    /// Malformed hex input
+    Hex(HexError),
+    /// Groth16 key, proof or verification error
+    Groth16(Groth16Error),
+    /// COBS framing error
+    Framing(FramingError),
+    /// Secure link error
+    #[cfg(feature = "stm32f4")]
+    Link(LinkError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "stm32f4")]
+            Error::Oled(e) => write!(f, "display error: {:?}", e),
+            Error::Ecdsa(e) => write!(f, "ecdsa error: {}", e),
+            Error::Hex(e) => write!(f, "hex error: {:?}", e),
+            Error::Groth16(e) => write!(f, "groth16 error: {:?}", e),
+            Error::Framing(e) => write!(f, "framing error: {:?}", e),
+            #[cfg(feature = "stm32f4")]
+            Error::Link(e) => write!(f, "secure link error: {:?}", e),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[cfg(feature = "stm32f4")]
+impl From<OledError> for Error {
+    fn from(e: OledError) -> Self {
+        Error::Oled(e)
+    }
+}
+
+impl From<ecdsa::Error> for Error {
+    fn from(e: ecdsa::Error) -> Self {
+        Error::Ecdsa(e)
+    }
+}
+
+impl From<HexError> for Error {
+    fn from(e: HexError) -> Self {
+        Error::Hex(e)
+    }
+}
+
+impl From<Groth16Error> for Error {
+    fn from(e: Groth16Error) -> Self {
+        Error::Groth16(e)
+    }
+}
+
+impl From<FramingError> for Error {
+    fn from(e: FramingError) -> Self {
+        Error::Framing(e)
+    }
+}
+
+#[cfg(feature = "stm32f4")]
+impl From<LinkError> for Error {
+    fn from(e: LinkError) -> Self {
+        Error::Link(e)
+    }
+}
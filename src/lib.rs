@@ -13,21 +13,77 @@ use alloc_cortex_m::CortexMHeap;
 #[global_allocator]
 pub static ALLOCATOR: CortexMHeap = CortexMHeap::empty();
 
+#[cfg(feature = "stm32f4")]
+pub mod board;
+#[cfg(feature = "stm32f4")]
+pub mod demos;
+mod error;
 pub mod utils;
 
+pub use error::Error;
+
 // Re-export commonly used items at the crate root for convenience
-pub use utils::oled::{DcPin, OledDisplay, RstPin};
+#[cfg(feature = "stm32f4")]
+pub use utils::oled::{
+    BitBangSpiInterface, DcPin, DisplaySize, DrawMode, EnablePin, I2cInterface, OledDisplay,
+    OledError, OledInterface, RstPin, ScrollSpeed, SpiInterface, TextConsole,
+};
+pub use utils::stack;
 
 // Custom getrandom implementation for no_std embedded targets
+//
+// `getrandom` draws from the source registered with `set_rng_source`, e.g.
+// the TRNG on an STM32F405/407. The STM32F411 has no RNG peripheral, so
+// without a registered source every request fails with `RNG_UNAVAILABLE`.
+//
+// DEMO ONLY: the `insecure-dwt-rng` feature falls back to the DWT cycle
+// counter instead. Its values are predictable, so anything derived from
+// them (keys, nonces, salts) must be treated as public.
+//
+// Cortex-M0/M0+ (`armv6m`, set by build.rs) has no cycle counter, so there
+// `set_rng_source` is the only way to supply entropy.
+use core::cell::Cell;
+use cortex_m::interrupt::{self, Mutex};
 use getrandom::register_custom_getrandom;
 
+#[cfg(all(feature = "insecure-dwt-rng", armv6m))]
+compile_error!("`insecure-dwt-rng` needs the DWT cycle counter, which Cortex-M0 lacks");
+
+/// Fills a buffer from a hardware entropy source
+pub type RngSource = fn(&mut [u8]) -> Result<(), getrandom::Error>;
+
+static RNG_SOURCE: Mutex<Cell<Option<RngSource>>> = Mutex::new(Cell::new(None));
+
+/// Custom error code: no entropy source has been registered
+pub const RNG_UNAVAILABLE: u32 = getrandom::Error::CUSTOM_START;
+
+/// Route `getrandom` through a hardware entropy source
+///
+/// Call once the peripheral is initialized. The source must fail rather
+/// than return weak bytes: a TRNG source polls DRDY for each word and, on a
+/// seed or clock error (SEIS/CEIS), clears the flag and retries a bounded
+/// number of times before giving up.
+pub fn set_rng_source(source: RngSource) {
+    interrupt::free(|cs| RNG_SOURCE.borrow(cs).set(Some(source)));
+}
+
 fn custom_getrandom(buf: &mut [u8]) -> Result<(), getrandom::Error> {
-    // Use DWT cycle counter as entropy source
-    // This is not cryptographically secure but works for embedded demos
-    use cortex_m::peripheral::DWT;
+    if let Some(source) = interrupt::free(|cs| RNG_SOURCE.borrow(cs).get()) {
+        return source(buf);
+    }
+
+    #[cfg(all(feature = "insecure-dwt-rng", not(armv6m)))]
+    return dwt_getrandom(buf);
+
+    #[cfg(not(all(feature = "insecure-dwt-rng", not(armv6m))))]
+    Err(core::num::NonZeroU32::new(RNG_UNAVAILABLE).unwrap().into())
+}
 
+/// Fill `buf` from the DWT cycle counter (predictable, demo only)
+#[cfg(all(feature = "insecure-dwt-rng", not(armv6m)))]
+fn dwt_getrandom(buf: &mut [u8]) -> Result<(), getrandom::Error> {
     for chunk in buf.chunks_mut(4) {
-        let random = DWT::cycle_count();
+        let random = cortex_m::peripheral::DWT::cycle_count();
         let bytes = random.to_le_bytes();
         for (i, byte) in chunk.iter_mut().enumerate() {
             *byte = bytes[i];
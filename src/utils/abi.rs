@@ -0,0 +1,89 @@
+//! Minimal Solidity ABI encoding for contract calls
+//!
+//! Supports the common static types, each encoded as a single 32-byte word,
+//! and a `FunctionCall` builder that prepends the 4-byte selector.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use super::crypto::keccak256;
+
+/// Compute a function selector: the first 4 bytes of `keccak256(signature)`
+///
+/// The signature must be canonical, e.g. `"transfer(address,uint256)"`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    let mut result = [0u8; 4];
+    result.copy_from_slice(&hash[0..4]);
+    result
+}
+
+/// Encode an `address` (20 bytes, left-padded to 32 bytes)
+pub fn encode_address(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..32].copy_from_slice(address);
+    word
+}
+
+/// Encode a `uint256` given as 32 big-endian bytes
+pub fn encode_uint256(value: &[u8; 32]) -> [u8; 32] {
+    *value
+}
+
+/// Encode a `bool` (0 or 1 in the last byte)
+pub fn encode_bool(value: bool) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[31] = value as u8;
+    word
+}
+
+/// Builder for contract calldata: selector followed by encoded arguments
+pub struct FunctionCall {
+    calldata: Vec<u8>,
+}
+
+impl FunctionCall {
+    /// Start a call to the function with the given canonical signature
+    pub fn new(signature: &str) -> Self {
+        let mut calldata = Vec::with_capacity(4 + 32 * 4);
+        calldata.extend_from_slice(&selector(signature));
+        FunctionCall { calldata }
+    }
+
+    /// Append an already-encoded 32-byte argument word
+    pub fn arg(mut self, word: [u8; 32]) -> Self {
+        self.calldata.extend_from_slice(&word);
+        self
+    }
+
+    /// Append an `address` argument
+    pub fn address(self, address: &[u8; 20]) -> Self {
+        self.arg(encode_address(address))
+    }
+
+    /// Append a `uint256` argument
+    pub fn uint256(self, value: &[u8; 32]) -> Self {
+        self.arg(encode_uint256(value))
+    }
+
+    /// Append a `bool` argument
+    pub fn bool(self, value: bool) -> Self {
+        self.arg(encode_bool(value))
+    }
+
+    /// Finish building and return the calldata
+    pub fn build(self) -> Vec<u8> {
+        self.calldata
+    }
+}
+
+/// Encode an ERC-20 `transfer(address,uint256)` call
+///
+/// Returns the 4-byte selector followed by the address and amount words.
+pub fn encode_transfer(to: &[u8; 20], amount: &[u8; 32]) -> [u8; 68] {
+    let mut calldata = [0u8; 68];
+    calldata[0..4].copy_from_slice(&selector("transfer(address,uint256)"));
+    calldata[4..36].copy_from_slice(&encode_address(to));
+    calldata[36..68].copy_from_slice(&encode_uint256(amount));
+    calldata
+}
@@ -0,0 +1,110 @@
+//! Machine-readable benchmark output
+//!
+//! With the `bench-csv` feature enabled, `report` prints one line per
+//! measurement over RTT, for example:
+//!
+//! ```text
+//! bench,groth16_verify,cycles=12345678,us=146972,ms=146
+//! ```
+//!
+//! A host script can grep for the `bench,` prefix and compare results
+//! across firmware versions. Without the feature `report` prints nothing,
+//! so the normal demo output is unchanged.
+//!
+//! `Benchmark` repeats an operation and always prints plain CSV rows that
+//! paste straight into a spreadsheet (see the `bench_all` binary):
+//!
+//! ```text
+//! name,iters,min,max,mean
+//! keccak256_1k,100,41234,41301,41250
+//! ```
+
+use cortex_m::peripheral::DWT;
+
+use crate::log;
+use crate::utils::timing::CycleTimer;
+
+/// Print a timing result as a `bench,...` line (only with `bench-csv`)
+///
+/// `name` should be a short identifier without commas or spaces.
+pub fn report(timer: &CycleTimer, name: &str, cycles: u32) {
+    if cfg!(feature = "bench-csv") {
+        log!(
+            "bench,{},cycles={},us={},ms={}",
+            name,
+            cycles,
+            timer.cycles_to_us(cycles),
+            timer.cycles_to_ms(cycles)
+        );
+    }
+}
+
+/// Cycle counts per iteration from one `Benchmark::run`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub iters: u32,
+    pub min: u32,
+    pub max: u32,
+    /// Rounded down
+    pub mean: u32,
+}
+
+/// Repeats operations under the DWT cycle counter and prints CSV rows
+///
+/// The header row is printed before the first result. Each iteration is
+/// timed on its own with a wrapping subtraction, so the counter may wrap
+/// any number of times over a long run; a single iteration must stay
+/// under 2^32 cycles (about 51 s at 84 MHz). The counter must be enabled
+/// first, as for `CycleTimer`.
+#[derive(Debug, Default)]
+pub struct Benchmark {
+    header_printed: bool,
+}
+
+impl Benchmark {
+    /// Start a new table; the header is printed on the first `run`
+    pub const fn new() -> Self {
+        Benchmark {
+            header_printed: false,
+        }
+    }
+
+    /// Time `iters` calls of `f` and print `name,iters,min,max,mean`
+    ///
+    /// `name` should not contain commas. An `iters` of 0 is run once.
+    pub fn run(&mut self, name: &str, iters: u32, mut f: impl FnMut()) -> BenchStats {
+        if !self.header_printed {
+            log!("name,iters,min,max,mean");
+            self.header_printed = true;
+        }
+
+        let iters = iters.max(1);
+        let mut min = u32::MAX;
+        let mut max = 0;
+        let mut total = 0u64;
+        for _ in 0..iters {
+            let start = DWT::cycle_count();
+            f();
+            let cycles = DWT::cycle_count().wrapping_sub(start);
+            min = min.min(cycles);
+            max = max.max(cycles);
+            total += cycles as u64;
+        }
+
+        let stats = BenchStats {
+            iters,
+            min,
+            max,
+            mean: (total / iters as u64) as u32,
+        };
+        log!(
+            "{},{},{},{},{}",
+            name,
+            stats.iters,
+            stats.min,
+            stats.max,
+            stats.mean
+        );
+        stats
+    }
+}
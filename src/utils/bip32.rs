@@ -0,0 +1,157 @@
+//! BIP-32 hierarchical deterministic key derivation over secp256k1
+//!
+//! Derives private keys from a BIP-39 seed along a path such as the
+//! standard Ethereum account path `m/44'/60'/0'/0/0`. Only private
+//! derivation is supported; extended public keys and xprv/xpub encoding
+//! are not.
+
+use hmac::{Hmac, Mac};
+use k256::{
+    elliptic_curve::{sec1::ToEncodedPoint, PrimeField},
+    NonZeroScalar, Scalar, SecretKey,
+};
+use sha2::Sha512;
+
+/// First hardened child index
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// HMAC key for the master key, fixed by BIP-32
+const MASTER_KEY: &[u8] = b"Bitcoin seed";
+
+/// Errors returned by key derivation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bip32Error {
+    /// The seed is not 16 to 64 bytes, or yields an invalid master key
+    InvalidSeed,
+    /// The derived key is zero or out of range; skip to the next index
+    InvalidChild,
+    /// The path string is malformed
+    InvalidPath,
+}
+
+/// A private key together with its chain code
+#[derive(Clone)]
+pub struct ExtendedKey {
+    secret_key: SecretKey,
+    chain_code: [u8; 32],
+    depth: u8,
+}
+
+impl ExtendedKey {
+    /// Derive the master key from a seed
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Bip32Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Bip32Error::InvalidSeed);
+        }
+        let (secret_key, chain_code) =
+            split_output(hmac_sha512(MASTER_KEY, &[seed])).ok_or(Bip32Error::InvalidSeed)?;
+
+        Ok(ExtendedKey {
+            secret_key,
+            chain_code,
+            depth: 0,
+        })
+    }
+
+    /// Derive a child key; indices from `HARDENED` up are hardened
+    pub fn derive_child(&self, index: u32) -> Result<Self, Bip32Error> {
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::InvalidChild)?;
+
+        // Hardened: 0x00 || k_par || index, normal: compressed point || index
+        let output = if index >= HARDENED {
+            let private = self.secret_key.to_bytes();
+            hmac_sha512(&self.chain_code, &[&[0], &private, &index.to_be_bytes()])
+        } else {
+            let point = self.secret_key.public_key().to_encoded_point(true);
+            hmac_sha512(&self.chain_code, &[point.as_bytes(), &index.to_be_bytes()])
+        };
+
+        let mut tweak = [0u8; 32];
+        tweak.copy_from_slice(&output[..32]);
+        let tweak = Option::<Scalar>::from(Scalar::from_repr(tweak.into()))
+            .ok_or(Bip32Error::InvalidChild)?;
+        let child = Option::<NonZeroScalar>::from(NonZeroScalar::new(
+            tweak + self.secret_key.to_nonzero_scalar().as_ref(),
+        ))
+        .ok_or(Bip32Error::InvalidChild)?;
+
+        let mut child_chain_code = [0u8; 32];
+        child_chain_code.copy_from_slice(&output[32..]);
+
+        Ok(ExtendedKey {
+            secret_key: SecretKey::from(child),
+            chain_code: child_chain_code,
+            depth,
+        })
+    }
+
+    /// Derive along a path such as `m/44'/60'/0'/0/0`
+    ///
+    /// Hardened components are marked with `'` or `h`. The path must start
+    /// at `m`, so this is normally called on the master key.
+    pub fn derive_path(&self, path: &str) -> Result<Self, Bip32Error> {
+        let mut components = path.split('/');
+        if components.next() != Some("m") {
+            return Err(Bip32Error::InvalidPath);
+        }
+
+        let mut key = self.clone();
+        for component in components {
+            key = key.derive_child(parse_index(component)?)?;
+        }
+        Ok(key)
+    }
+
+    /// Big-endian private key, as taken by `EthereumKeyPair::from_private_key_bytes`
+    pub fn to_private_key_bytes(&self) -> [u8; 32] {
+        self.secret_key.to_bytes().into()
+    }
+
+    /// Chain code used to derive this key's children
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Number of derivation steps from the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+}
+
+/// Parse one path component, e.g. `44'` or `0`
+fn parse_index(component: &str) -> Result<u32, Bip32Error> {
+    let (digits, hardened) = match component
+        .strip_suffix('\'')
+        .or_else(|| component.strip_suffix('h'))
+    {
+        Some(digits) => (digits, true),
+        None => (component, false),
+    };
+    if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(Bip32Error::InvalidPath);
+    }
+
+    let index: u32 = digits.parse().map_err(|_| Bip32Error::InvalidPath)?;
+    if index >= HARDENED {
+        return Err(Bip32Error::InvalidPath);
+    }
+    Ok(if hardened { index | HARDENED } else { index })
+}
+
+/// HMAC-SHA512 over the concatenation of `parts`
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Split an HMAC output into a private key and chain code
+fn split_output(output: [u8; 64]) -> Option<(SecretKey, [u8; 32])> {
+    let (private, chain) = output.split_at(32);
+    let secret_key = SecretKey::from_slice(private).ok()?;
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(chain);
+    Some((secret_key, chain_code))
+}
@@ -0,0 +1,74 @@
+//! Debounced push button
+//!
+//! Pin connections:
+//! - Button → PA0 (KEY on the BlackPill, shorts to GND; use the internal pull-up)
+//!
+//! The button is active low. A press or release only counts once the
+//! input has held its new level for the whole debounce window.
+
+use stm32f4xx_hal::hal::{delay::DelayNs, digital::InputPin};
+
+use super::delay::Delay;
+
+/// Default time the input must stay stable, in milliseconds
+pub const DEBOUNCE_MS: u32 = 20;
+
+/// Active-low push button with software debounce
+pub struct Button<PIN> {
+    pin: PIN,
+    delay: Delay,
+    debounce_ms: u32,
+}
+
+impl<PIN: InputPin> Button<PIN> {
+    /// Take ownership of a pulled-up input pin
+    pub fn new(pin: PIN) -> Self {
+        Button {
+            pin,
+            delay: Delay::new(),
+            debounce_ms: DEBOUNCE_MS,
+        }
+    }
+
+    /// Use a different debounce window
+    pub fn with_debounce_ms(mut self, debounce_ms: u32) -> Self {
+        self.debounce_ms = debounce_ms;
+        self
+    }
+
+    /// Whether the button is held right now, without debouncing
+    ///
+    /// Takes `&mut self` because embedded-hal 1.0 reads inputs through a
+    /// mutable reference. A pin that fails to read counts as released.
+    pub fn is_pressed(&mut self) -> bool {
+        self.pin.is_low().unwrap_or(false)
+    }
+
+    /// Block until the button is pressed
+    pub fn wait_for_press(&mut self) {
+        self.wait_for(true);
+    }
+
+    /// Block until the button is released
+    pub fn wait_for_release(&mut self) {
+        self.wait_for(false);
+    }
+
+    /// Block until the input settles at `pressed` for a debounce window
+    ///
+    /// The input is sampled every millisecond, and any sample at the other
+    /// level restarts the window.
+    fn wait_for(&mut self, pressed: bool) {
+        let mut stable_ms = 0;
+        loop {
+            if self.is_pressed() != pressed {
+                stable_ms = 0;
+            } else if stable_ms == self.debounce_ms {
+                return;
+            } else {
+                self.delay.delay_ms(1);
+                stable_ms += 1;
+            }
+        }
+    }
+}
@@ -1,50 +1,66 @@
 //! Cryptographic utilities for Ethereum ECDSA operations
 
+extern crate alloc;
+use alloc::vec::Vec;
+
 use k256::{
-    ecdsa::{signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey},
+    ecdsa::{
+        signature::hazmat::PrehashSigner, signature::Signer, signature::Verifier, RecoveryId,
+        Signature, SigningKey, VerifyingKey,
+    },
     elliptic_curve::{
         rand_core::{CryptoRng, RngCore as RngCore06},
         sec1::ToEncodedPoint,
     },
-    PublicKey,
+    NonZeroScalar, PublicKey, Scalar,
 };
-use sha3::{Digest, Keccak256};
+use sha3::{
+    digest::{ExtendableOutput, XofReader},
+    Digest, Keccak256, Shake256,
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 // Also import rand_core 0.9 for miden-crypto compatibility
 use rand_core::RngCore as RngCore09;
 
-/// Simple RNG implementation using hardware timer as entropy source
-pub struct SimpleRng {
-    state: u32,
-    counter: u32,
+/// Reproducible, non-cryptographic PRNG for demos and tests
+///
+/// The same seed always yields the same sequence, which is handy for
+/// comparing runs (e.g. timing the same keys every time). It is SplitMix64
+/// and trivially predictable, so it deliberately does not implement
+/// `CryptoRng`. See `InsecureCryptoRng` for feeding it to key generation in
+/// a demo.
+pub struct DemoRng {
+    state: u64,
 }
 
-impl SimpleRng {
+impl DemoRng {
     pub fn new(seed: u32) -> Self {
-        SimpleRng {
-            state: seed,
-            counter: 0,
-        }
+        DemoRng { state: seed as u64 }
+    }
+
+    fn next_u64_internal(&mut self) -> u64 {
+        // SplitMix64: a Weyl sequence through a 64-bit finalizer
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
     }
 
     fn next_u32_internal(&mut self) -> u32 {
-        // Simple LCG (Linear Congruential Generator) with counter mixing
-        self.counter = self.counter.wrapping_add(1);
-        self.state = self.state.wrapping_mul(1664525).wrapping_add(1013904223);
-        self.state ^ self.counter
+        (self.next_u64_internal() >> 32) as u32
     }
 }
 
 // Implement RngCore 0.6 for k256 compatibility
-impl RngCore06 for SimpleRng {
+impl RngCore06 for DemoRng {
     fn next_u32(&mut self) -> u32 {
         self.next_u32_internal()
     }
 
     fn next_u64(&mut self) -> u64 {
-        let high = self.next_u32_internal() as u64;
-        let low = self.next_u32_internal() as u64;
-        (high << 32) | low
+        self.next_u64_internal()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
@@ -67,15 +83,13 @@ impl RngCore06 for SimpleRng {
 }
 
 // Implement RngCore 0.9 for miden-crypto compatibility
-impl RngCore09 for SimpleRng {
+impl RngCore09 for DemoRng {
     fn next_u32(&mut self) -> u32 {
         self.next_u32_internal()
     }
 
     fn next_u64(&mut self) -> u64 {
-        let high = self.next_u32_internal() as u64;
-        let low = self.next_u32_internal() as u64;
-        (high << 32) | low
+        self.next_u64_internal()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
@@ -89,8 +103,47 @@ impl RngCore09 for SimpleRng {
     }
 }
 
-// Mark our RNG as cryptographically secure (for demo purposes)
-impl CryptoRng for SimpleRng {}
+/// Wrapper that claims `CryptoRng` for an RNG that is NOT secure
+///
+/// Exists only so demos can generate keys from a reproducible `DemoRng`.
+/// Anyone who knows or guesses the seed can recompute those keys, so never
+/// use them to protect anything of value.
+pub struct InsecureCryptoRng<R>(R);
+
+impl<R> InsecureCryptoRng<R> {
+    /// Wrap an RNG, opting in to treating it as cryptographically secure
+    pub fn new(rng: R) -> Self {
+        InsecureCryptoRng(rng)
+    }
+
+    /// Unwrap the inner RNG
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R: RngCore06> RngCore06 for InsecureCryptoRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(
+        &mut self,
+        dest: &mut [u8],
+    ) -> Result<(), k256::elliptic_curve::rand_core::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl<R: RngCore06> CryptoRng for InsecureCryptoRng<R> {}
 
 /// Helper function to convert bytes to hex string
 pub fn bytes_to_hex_string<'a>(bytes: &[u8], buffer: &'a mut [u8]) -> &'a str {
@@ -104,30 +157,217 @@ pub fn bytes_to_hex_string<'a>(bytes: &[u8], buffer: &'a mut [u8]) -> &'a str {
     core::str::from_utf8(&buffer[..i]).unwrap()
 }
 
+/// Format an address with its EIP-55 mixed-case checksum, `0x`-prefixed
+///
+/// Each letter of the lowercase hex is uppercased when the matching nibble
+/// of `keccak256(lowercase_hex)` is 8 or more.
+pub fn to_checksum_address<'a>(address: &[u8; 20], out: &'a mut [u8; 42]) -> &'a str {
+    const HEX_CHARS: &[u8] = b"0123456789abcdef";
+    out[0] = b'0';
+    out[1] = b'x';
+    for (i, &byte) in address.iter().enumerate() {
+        out[2 + 2 * i] = HEX_CHARS[(byte >> 4) as usize];
+        out[3 + 2 * i] = HEX_CHARS[(byte & 0x0F) as usize];
+    }
+
+    let hash = keccak256(&out[2..]);
+    for (i, c) in out[2..].iter_mut().enumerate() {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0F
+        };
+        if nibble >= 8 {
+            c.make_ascii_uppercase();
+        }
+    }
+
+    core::str::from_utf8(&out[..]).unwrap()
+}
+
+/// Errors returned by hex decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexError {
+    /// The number of hex digits is odd
+    OddLength,
+    /// A non-hex character was found at this byte offset of the input
+    InvalidChar(usize),
+    /// The output buffer is too small for the decoded bytes
+    OutputTooSmall,
+    /// The input does not decode to exactly the expected number of bytes
+    WrongLength,
+}
+
+/// Decode one hex digit
+fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Helper function to parse a hex string into bytes
+///
+/// Surrounding ASCII whitespace and an optional `0x`/`0X` prefix are
+/// ignored. Returns the number of bytes written to `out`. Error positions
+/// are byte offsets into the original `input`.
+pub fn hex_to_bytes(input: &str, out: &mut [u8]) -> Result<usize, HexError> {
+    let trimmed = input.trim_matches(|c: char| c.is_ascii_whitespace());
+    let mut offset = input.len()
+        - input
+            .trim_start_matches(|c: char| c.is_ascii_whitespace())
+            .len();
+
+    let digits = match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(rest) => {
+            offset += 2;
+            rest.as_bytes()
+        }
+        None => trimmed.as_bytes(),
+    };
+
+    // Report bad characters before length problems so the position is useful
+    for (i, &c) in digits.iter().enumerate() {
+        if hex_digit(c).is_none() {
+            return Err(HexError::InvalidChar(offset + i));
+        }
+    }
+    if digits.len() % 2 != 0 {
+        return Err(HexError::OddLength);
+    }
+    let len = digits.len() / 2;
+    if len > out.len() {
+        return Err(HexError::OutputTooSmall);
+    }
+
+    for (byte, pair) in out.iter_mut().zip(digits.chunks_exact(2)) {
+        // Both digits were validated above
+        *byte = (hex_digit(pair[0]).unwrap_or(0) << 4) | hex_digit(pair[1]).unwrap_or(0);
+    }
+
+    Ok(len)
+}
+
+/// Parse a hex string of exactly `N` bytes, e.g. an address or a hash
+///
+/// Accepts the same input as `hex_to_bytes`.
+pub fn hex_to_array<const N: usize>(input: &str) -> Result<[u8; N], HexError> {
+    let mut out = [0u8; N];
+    match hex_to_bytes(input, &mut out) {
+        Ok(len) if len == N => Ok(out),
+        Ok(_) | Err(HexError::OutputTooSmall) => Err(HexError::WrongLength),
+        Err(err) => Err(err),
+    }
+}
+
+/// Counterpart to `bytes_to_hex_string`; same as `hex_to_bytes`
+pub fn hex_decode(input: &str, out: &mut [u8]) -> Result<usize, HexError> {
+    hex_to_bytes(input, out)
+}
+
+/// Fixed-size counterpart to `hex_decode`; same as `hex_to_array`
+pub fn hex_decode_fixed<const N: usize>(input: &str) -> Result<[u8; N], HexError> {
+    hex_to_array(input)
+}
+
+/// Errors returned when importing keys and recovering or checking signers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoError {
+    /// The scalar is zero or not below the curve order
+    InvalidPrivateKey,
+    /// The decoded key is not exactly 32 bytes
+    InvalidLength,
+    /// The key is not valid hex
+    Hex(HexError),
+    /// `v` is not 0, 1, 27 or 28
+    InvalidRecoveryId,
+    /// `r` or `s` is out of range, or no key recovers from the signature
+    InvalidSignature,
+    /// The threshold is zero or larger than the number of signers
+    InvalidThreshold,
+    /// A signature recovers to an address that is not an authorized signer
+    UnauthorizedSigner,
+    /// Two signatures recover to the same signer
+    DuplicateSigner,
+    /// Fewer valid signatures than the threshold
+    BelowThreshold,
+    /// The nonce is not above the last one accepted (a replay)
+    StaleNonce,
+    /// `s` is above n/2, which Ethereum rejects since EIP-2
+    MalleableSignature,
+}
+
+impl From<HexError> for CryptoError {
+    fn from(err: HexError) -> Self {
+        CryptoError::Hex(err)
+    }
+}
+
 /// Ethereum key pair structure
+///
+/// The compressed public key and the address are computed once at
+/// construction, so fetching them repeatedly (e.g. for every signed
+/// envelope) costs only a copy.
+///
+/// `zeroize` scrubs the key pair in place and runs again on drop. Copies
+/// handed out by `private_key_bytes`, or left behind on the stack by moves,
+/// are the caller's to scrub.
 pub struct EthereumKeyPair {
     pub signing_key: SigningKey,
     pub verifying_key: VerifyingKey,
     pub public_key: PublicKey,
     pub address: [u8; 20],
+    public_key_compressed: [u8; 33],
 }
 
 impl EthereumKeyPair {
     /// Generate a new Ethereum key pair using the provided RNG
     pub fn generate<R: RngCore06 + CryptoRng>(rng: &mut R) -> Self {
-        let signing_key = SigningKey::random(rng);
+        Self::from_signing_key(SigningKey::random(rng))
+    }
+
+    /// Build a key pair from an existing signing key
+    pub fn from_signing_key(signing_key: SigningKey) -> Self {
         let verifying_key = VerifyingKey::from(&signing_key);
         let public_key = PublicKey::from(&verifying_key);
         let address = Self::derive_address(&public_key);
 
+        let mut public_key_compressed = [0u8; 33];
+        public_key_compressed.copy_from_slice(public_key.to_encoded_point(true).as_bytes());
+
         EthereumKeyPair {
             signing_key,
             verifying_key,
             public_key,
             address,
+            public_key_compressed,
         }
     }
 
+    /// Import a key pair from a 32-byte big-endian private key
+    pub fn from_private_key_bytes(bytes: &[u8; 32]) -> Result<Self, CryptoError> {
+        let signing_key =
+            SigningKey::from_bytes(bytes.into()).map_err(|_| CryptoError::InvalidPrivateKey)?;
+        Ok(Self::from_signing_key(signing_key))
+    }
+
+    /// Import a key pair from a hex private key, with or without `0x`
+    pub fn from_private_key_hex(hex: &str) -> Result<Self, CryptoError> {
+        let mut bytes = [0u8; 32];
+        let result = match hex_to_bytes(hex, &mut bytes) {
+            Ok(32) => Self::from_private_key_bytes(&bytes),
+            Ok(_) | Err(HexError::OutputTooSmall) => Err(CryptoError::InvalidLength),
+            Err(err) => Err(err.into()),
+        };
+        bytes.fill(0);
+        result
+    }
+
     /// Derive Ethereum address from public key
     fn derive_address(public_key: &PublicKey) -> [u8; 20] {
         // Get uncompressed public key (65 bytes: 0x04 + x + y)
@@ -154,6 +394,64 @@ impl EthereumKeyPair {
         self.verifying_key.verify(message, signature)
     }
 
+    /// Verify a signature, reporting a high-s one as `MalleableSignature`
+    ///
+    /// k256 already refuses high-s signatures in `verify`, but with the same
+    /// opaque error as any other failure. This checks `s <= n/2` first so
+    /// the caller can tell a malleated signature from a wrong one.
+    pub fn verify_strict(&self, message: &[u8], signature: &Signature) -> Result<(), CryptoError> {
+        if !is_low_s(signature) {
+            return Err(CryptoError::MalleableSignature);
+        }
+        self.verify(message, signature)
+            .map_err(|_| CryptoError::InvalidSignature)
+    }
+
+    /// Sign `keccak256(message)` with an RFC6979 deterministic nonce
+    ///
+    /// No RNG is involved: the same key and message always give the same
+    /// low-s signature, so outputs can be compared across builds.
+    pub fn sign_deterministic(&self, message: &[u8]) -> Signature {
+        self.signing_key
+            .sign_prehash(&keccak256(message))
+            .expect("ECDSA signing failed")
+    }
+
+    /// Sign `keccak256(message)`, returning the recovery id alongside
+    ///
+    /// The signature is low-s, with the recovery id adjusted to match, so
+    /// `signature_to_eth_bytes` yields the same `v` as go-ethereum.
+    pub fn sign_recoverable(&self, message: &[u8]) -> (Signature, RecoveryId) {
+        self.signing_key
+            .sign_digest_recoverable(Keccak256::new_with_prefix(message))
+            .expect("ECDSA signing failed")
+    }
+
+    /// Sign a 32-byte hash directly, returning the recovery id alongside
+    ///
+    /// The signature is normalized to low-s, with the recovery id adjusted
+    /// to match.
+    pub fn sign_hash_recoverable(
+        &self,
+        hash: &[u8; 32],
+    ) -> Result<(Signature, RecoveryId), ecdsa::Error> {
+        self.signing_key.sign_prehash_recoverable(hash)
+    }
+
+    /// Overwrite the private scalar and forget the account
+    ///
+    /// The signing key becomes the scalar 1 (the old one is zeroed as it
+    /// drops), the public keys follow it, and the cached address and
+    /// compressed key are zeroed, so nothing reports the old identity.
+    /// Signatures made afterwards are worthless.
+    pub fn zeroize(&mut self) {
+        self.signing_key = SigningKey::from(NonZeroScalar::new(Scalar::ONE).unwrap());
+        self.verifying_key = *self.signing_key.verifying_key();
+        self.public_key = PublicKey::from(&self.verifying_key);
+        self.address = [0; 20];
+        self.public_key_compressed = [0; 33];
+    }
+
     /// Get the private key bytes
     pub fn private_key_bytes(&self) -> [u8; 32] {
         self.signing_key.to_bytes().into()
@@ -161,88 +459,398 @@ impl EthereumKeyPair {
 
     /// Get the compressed public key bytes
     pub fn public_key_compressed_bytes(&self) -> [u8; 33] {
-        let encoded = self.public_key.to_encoded_point(true);
-        let bytes = encoded.as_bytes();
-        let mut result = [0u8; 33];
-        result.copy_from_slice(bytes);
-        result
+        self.public_key_compressed
+    }
+
+    /// Borrow the cached compressed public key
+    pub fn public_key_compressed(&self) -> &[u8; 33] {
+        &self.public_key_compressed
     }
 }
 
+/// Verifier-only counterpart to `EthereumKeyPair`
+///
+/// Holds just the public half, so devices that only check signatures never
+/// need to carry a secret key.
+pub struct EthereumVerifier {
+    pub verifying_key: VerifyingKey,
+    pub address: [u8; 20],
+}
+
+impl EthereumVerifier {
+    /// Create a verifier from a secp256k1 public key
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        EthereumVerifier {
+            verifying_key: VerifyingKey::from(public_key),
+            address: EthereumKeyPair::derive_address(public_key),
+        }
+    }
+
+    /// Create a verifier from SEC1 compressed public key bytes
+    pub fn from_compressed_bytes(bytes: &[u8; 33]) -> Result<Self, ecdsa::Error> {
+        let public_key = PublicKey::from_sec1_bytes(bytes).map_err(|_| ecdsa::Error::new())?;
+        Ok(Self::from_public_key(&public_key))
+    }
+
+    /// Get the Ethereum address of the public key
+    pub fn address(&self) -> [u8; 20] {
+        self.address
+    }
+
+    /// Verify a signature
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), ecdsa::Error> {
+        self.verifying_key.verify(message, signature)
+    }
+
+    /// Verify an EIP712 typed data signature
+    pub fn verify_eip712(
+        &self,
+        domain: &Eip712Domain,
+        message: &impl Eip712Struct,
+        signature: &Signature,
+    ) -> Result<(), ecdsa::Error> {
+        let message_hash = eip712_hash(domain, &message.hash_struct());
+        self.verifying_key.verify(&message_hash, signature)
+    }
+}
+
+impl Drop for EthereumKeyPair {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Whether `s` is at most n/2, the canonical form required by EIP-2
+pub fn is_low_s(signature: &Signature) -> bool {
+    signature.normalize_s().is_none()
+}
+
+/// Encode a signature as ASN.1 DER, as expected by OpenSSL, Bitcoin and X.509
+pub fn signature_to_der(signature: &Signature) -> Vec<u8> {
+    signature.to_der().as_bytes().to_vec()
+}
+
+/// Decode an ASN.1 DER signature
+pub fn signature_from_der(der: &[u8]) -> Result<Signature, ecdsa::Error> {
+    Signature::from_der(der)
+}
+
+/// Encode a recoverable signature in the EIP-2098 compact form
+///
+/// Returns `r || yParityAndS`, where the recovery parity is packed into the
+/// top bit of `s`. A high-s signature is normalized first (flipping the
+/// parity), since the packing relies on the top bit of a low `s` being 0.
+pub fn signature_to_eip2098(signature: &Signature, recovery_id: RecoveryId) -> [u8; 64] {
+    let (signature, y_odd) = match signature.normalize_s() {
+        Some(normalized) => (normalized, !recovery_id.is_y_odd()),
+        None => (*signature, recovery_id.is_y_odd()),
+    };
+
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&signature.to_bytes());
+    if y_odd {
+        compact[32] |= 0x80;
+    }
+    compact
+}
+
+/// Decode an EIP-2098 compact signature into the signature and recovery id
+pub fn signature_from_eip2098(compact: &[u8; 64]) -> Result<(Signature, RecoveryId), ecdsa::Error> {
+    let y_odd = compact[32] & 0x80 != 0;
+    let mut bytes = *compact;
+    bytes[32] &= 0x7F;
+
+    let signature = Signature::from_slice(&bytes)?;
+    Ok((signature, RecoveryId::new(y_odd, false)))
+}
+
+/// Pack a recoverable signature as Ethereum's 65-byte `r || s || v`
+///
+/// `v` is `27 + y_parity`. A high-s signature is normalized first
+/// (flipping the parity), since Ethereum rejects high-s signatures.
+pub fn signature_to_eth_bytes(signature: &Signature, recovery_id: RecoveryId) -> [u8; 65] {
+    let (signature, y_odd) = match signature.normalize_s() {
+        Some(normalized) => (normalized, !recovery_id.is_y_odd()),
+        None => (*signature, recovery_id.is_y_odd()),
+    };
+
+    let mut bytes = [0u8; 65];
+    bytes[..64].copy_from_slice(&signature.to_bytes());
+    bytes[64] = 27 + y_odd as u8;
+    bytes
+}
+
+/// Recover the signer's address from a signature over `keccak256(message)`
+pub fn recover_address(
+    message: &[u8],
+    signature: &Signature,
+    recovery_id: RecoveryId,
+) -> Result<[u8; 20], ecdsa::Error> {
+    recover_address_from_hash(&keccak256(message), signature, recovery_id)
+}
+
+/// Recover the signer's address from a signature over a 32-byte hash
+///
+/// This is Ethereum's `ecrecover`.
+pub fn recover_address_from_hash(
+    hash: &[u8; 32],
+    signature: &Signature,
+    recovery_id: RecoveryId,
+) -> Result<[u8; 20], ecdsa::Error> {
+    let verifying_key = VerifyingKey::recover_from_prehash(hash, signature, recovery_id)?;
+    Ok(EthereumKeyPair::derive_address(&PublicKey::from(
+        &verifying_key,
+    )))
+}
+
+/// Recover the signer's address from a 65-byte `r || s || v` signature
+///
+/// Mirrors Solidity's `ecrecover`, except that high-s signatures are
+/// rejected as in EIP-2. `v` may be 27/28 or the raw parity 0/1.
+pub fn ecrecover(message_hash: &[u8; 32], sig_bytes: &[u8; 65]) -> Result<[u8; 20], CryptoError> {
+    let y_odd = match sig_bytes[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return Err(CryptoError::InvalidRecoveryId),
+    };
+    let signature =
+        Signature::from_slice(&sig_bytes[..64]).map_err(|_| CryptoError::InvalidSignature)?;
+
+    recover_address_from_hash(message_hash, &signature, RecoveryId::new(y_odd, false))
+        .map_err(|_| CryptoError::InvalidSignature)
+}
+
 /// Hash a message using Keccak256 (Ethereum standard)
 pub fn keccak256(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
+    let mut hasher = Keccak256Hasher::new();
     hasher.update(data);
-    let hash = hasher.finalize();
-    let mut result = [0u8; 32];
-    result.copy_from_slice(&hash);
-    result
+    hasher.finalize()
+}
+
+/// Incremental Keccak256, for input that is not in one contiguous buffer
+///
+/// Feeding the same bytes in any number of `update` calls gives the same
+/// result as `keccak256` over their concatenation.
+#[derive(Clone, Default)]
+pub struct Keccak256Hasher {
+    inner: Keccak256,
+}
+
+impl Keccak256Hasher {
+    /// Start a new hash
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Absorb more input
+    pub fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.inner, data);
+    }
+
+    /// Finish and return the 32-byte digest
+    pub fn finalize(self) -> [u8; 32] {
+        self.inner.finalize().into()
+    }
+}
+
+/// Commit to a value for a commit-reveal scheme
+///
+/// Returns `keccak256(value || salt)`, the same ordering as
+/// `keccak256(abi.encodePacked(value, salt))` in Solidity. The salt must be
+/// random and kept secret until the reveal, or short values can be guessed.
+pub fn commit(value: &[u8], salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(value);
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+/// Check a revealed value and salt against an earlier commitment
+///
+/// The comparison is constant-time.
+pub fn verify_reveal(commitment: &[u8; 32], value: &[u8], salt: &[u8; 32]) -> bool {
+    commit(value, salt).ct_eq(commitment).into()
+}
+
+/// Compare two byte strings in constant time
+///
+/// Use this rather than `==` for addresses, hashes and MACs that gate an
+/// action, so response timing does not reveal how many leading bytes
+/// matched. Slices of different lengths are unequal; only the length
+/// itself may leak.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Compare two addresses in constant time (see `ct_eq`)
+pub fn addresses_equal(a: &[u8; 20], b: &[u8; 20]) -> bool {
+    ct_eq(a, b)
+}
+
+/// Hash data with the SHAKE256 extendable-output function
+///
+/// Fills the whole of `out`, so any output length can be produced.
+pub fn shake256(input: &[u8], out: &mut [u8]) {
+    let mut hasher = Shake256::default();
+    sha3::digest::Update::update(&mut hasher, input);
+    hasher.finalize_xof().read(out);
 }
 
 /// EIP712 Domain structure
+///
+/// `chainId` and `verifyingContract` are optional. Omitted fields are left
+/// out of both the `EIP712Domain(...)` type string and the encoding, so a
+/// `name`+`version`-only domain can sign chain-agnostic messages such as
+/// login challenges.
 #[derive(Clone)]
 pub struct Eip712Domain {
     pub name: &'static str,
     pub version: &'static str,
-    pub chain_id: u64,
-    pub verifying_contract: [u8; 20],
+    /// Big-endian uint256 chain ID
+    pub chain_id: Option<[u8; 32]>,
+    pub verifying_contract: Option<[u8; 20]>,
 }
 
 impl Eip712Domain {
-    /// Create a new EIP712 domain
+    /// Create a new EIP712 domain bound to a chain and verifying contract
     pub fn new(
+        name: &'static str,
+        version: &'static str,
+        chain_id: [u8; 32],
+        verifying_contract: [u8; 20],
+    ) -> Self {
+        Self {
+            name,
+            version,
+            chain_id: Some(chain_id),
+            verifying_contract: Some(verifying_contract),
+        }
+    }
+
+    /// Create a domain for a chain ID that fits in a `u64`
+    pub fn with_chain_id_u64(
         name: &'static str,
         version: &'static str,
         chain_id: u64,
         verifying_contract: [u8; 20],
     ) -> Self {
+        let mut chain_id_bytes = [0u8; 32];
+        chain_id_bytes[24..].copy_from_slice(&chain_id.to_be_bytes());
+        Self::new(name, version, chain_id_bytes, verifying_contract)
+    }
+
+    /// Create a domain with only `name` and `version`
+    pub fn name_version(name: &'static str, version: &'static str) -> Self {
         Self {
             name,
             version,
-            chain_id,
-            verifying_contract,
+            chain_id: None,
+            verifying_contract: None,
+        }
+    }
+
+    /// Compute the `EIP712Domain(...)` type hash for the fields present
+    pub fn type_hash(&self) -> [u8; 32] {
+        // Longest form is 82 bytes
+        let mut type_string = [0u8; 96];
+        let mut len = 0;
+        let mut push = |part: &[u8]| {
+            type_string[len..len + part.len()].copy_from_slice(part);
+            len += part.len();
+        };
+
+        push(b"EIP712Domain(string name,string version");
+        if self.chain_id.is_some() {
+            push(b",uint256 chainId");
         }
+        if self.verifying_contract.is_some() {
+            push(b",address verifyingContract");
+        }
+        push(b")");
+
+        keccak256(&type_string[..len])
     }
 
     /// Compute the domain separator hash
     pub fn hash_struct(&self) -> [u8; 32] {
-        // EIP712Domain type hash
-        let type_hash = keccak256(
-            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
-        );
-
         let name_hash = keccak256(self.name.as_bytes());
         let version_hash = keccak256(self.version.as_bytes());
 
-        // Encode: typeHash || nameHash || versionHash || chainId || verifyingContract
+        // Encode: typeHash || nameHash || versionHash [|| chainId] [|| verifyingContract]
         let mut encoded = [0u8; 160]; // 32 + 32 + 32 + 32 + 32 (padded address)
-        encoded[0..32].copy_from_slice(&type_hash);
+        encoded[0..32].copy_from_slice(&self.type_hash());
         encoded[32..64].copy_from_slice(&name_hash);
         encoded[64..96].copy_from_slice(&version_hash);
+        let mut len = 96;
 
         // Encode chain_id as uint256 (32 bytes, big-endian)
-        let chain_id_bytes = self.chain_id.to_be_bytes();
-        encoded[120..128].copy_from_slice(&chain_id_bytes);
+        if let Some(chain_id) = self.chain_id {
+            encoded[len..len + 32].copy_from_slice(&chain_id);
+            len += 32;
+        }
 
         // Encode address (20 bytes, left-padded to 32 bytes)
-        encoded[140..160].copy_from_slice(&self.verifying_contract);
+        if let Some(verifying_contract) = self.verifying_contract {
+            encoded[len + 12..len + 32].copy_from_slice(&verifying_contract);
+            len += 32;
+        }
 
-        keccak256(&encoded)
+        keccak256(&encoded[..len])
     }
 }
 
+/// Largest `encodeData` output supported by `Eip712Struct::hash_struct`
+pub const EIP712_MAX_ENCODED_LEN: usize = 16 * 32;
+
+/// A typed struct that can be hashed and signed under EIP712
+///
+/// `encode_data` writes each member as a 32-byte word in declaration order:
+/// atomic values are left-padded (addresses, unsigned integers) or hashed
+/// (`bytes`, `string`), an array is hashed over its encoded elements (see
+/// `encode_uint256_array`), and a nested struct member is encoded as its own
+/// `hash_struct()`. Types referenced by nested members must be appended to
+/// the primary type string, sorted by name, before hashing it in `type_hash`.
+pub trait Eip712Struct {
+    /// Hash of the encoded type string
+    fn type_hash() -> [u8; 32];
+
+    /// Encode the members into `out`, returning the number of bytes written
+    fn encode_data(&self, out: &mut [u8]) -> usize;
+
+    /// Compute `keccak256(typeHash || encodeData(s))`
+    fn hash_struct(&self) -> [u8; 32] {
+        let mut encoded = [0u8; 32 + EIP712_MAX_ENCODED_LEN];
+        encoded[..32].copy_from_slice(&Self::type_hash());
+        let len = self.encode_data(&mut encoded[32..]);
+        keccak256(&encoded[..32 + len])
+    }
+}
+
+/// Encode a `uint256[]` member as its EIP712 word
+///
+/// Each element is already a 32-byte big-endian word, so the encoding is
+/// `keccak256(values[0] || values[1] || ...)`. An empty array hashes to
+/// `keccak256("")`.
+pub fn encode_uint256_array(values: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Keccak256Hasher::new();
+    for value in values {
+        hasher.update(value);
+    }
+    hasher.finalize()
+}
+
 /// EIP712 Multisig Transaction structure
 #[derive(Clone)]
-pub struct MultisigTransaction {
+pub struct MultisigTransaction<'a> {
     pub to: [u8; 20],
     pub value: u64,
-    pub data: &'static [u8],
+    pub data: &'a [u8],
     pub nonce: u64,
 }
 
-impl MultisigTransaction {
+impl<'a> MultisigTransaction<'a> {
     /// Create a new multisig transaction
-    pub fn new(to: [u8; 20], value: u64, data: &'static [u8], nonce: u64) -> Self {
+    pub fn new(to: [u8; 20], value: u64, data: &'a [u8], nonce: u64) -> Self {
         Self {
             to,
             value,
@@ -250,58 +858,141 @@ impl MultisigTransaction {
             nonce,
         }
     }
+}
 
-    /// Compute the struct hash for this transaction
-    pub fn hash_struct(&self) -> [u8; 32] {
-        // MultisigTransaction type hash
-        let type_hash =
-            keccak256(b"MultisigTransaction(address to,uint256 value,bytes data,uint256 nonce)");
+impl Eip712Struct for MultisigTransaction<'_> {
+    fn type_hash() -> [u8; 32] {
+        keccak256(b"MultisigTransaction(address to,uint256 value,bytes data,uint256 nonce)")
+    }
 
-        let data_hash = keccak256(self.data);
+    fn encode_data(&self, out: &mut [u8]) -> usize {
+        // Encode: to || value || dataHash || nonce
+        out[..128].fill(0);
+        out[12..32].copy_from_slice(&self.to);
+        out[56..64].copy_from_slice(&self.value.to_be_bytes());
+        out[64..96].copy_from_slice(&keccak256(self.data));
+        out[120..128].copy_from_slice(&self.nonce.to_be_bytes());
+        128
+    }
+}
 
-        // Encode: typeHash || to || value || dataHash || nonce
-        let mut encoded = [0u8; 160]; // 32 + 32 + 32 + 32 + 32
-        encoded[0..32].copy_from_slice(&type_hash);
+/// EIP-2612 `Permit` approval, signed off-chain in place of `approve`
+#[derive(Clone)]
+pub struct Permit {
+    pub owner: [u8; 20],
+    pub spender: [u8; 20],
+    /// Allowance as a big-endian uint256
+    pub value: [u8; 32],
+    pub nonce: u64,
+    pub deadline: u64,
+}
 
-        // Encode address (20 bytes, left-padded to 32 bytes)
-        encoded[44..64].copy_from_slice(&self.to);
+impl Eip712Struct for Permit {
+    fn type_hash() -> [u8; 32] {
+        keccak256(
+            b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+        )
+    }
 
-        // Encode value as uint256 (32 bytes, big-endian)
-        let value_bytes = self.value.to_be_bytes();
-        encoded[88..96].copy_from_slice(&value_bytes);
+    fn encode_data(&self, out: &mut [u8]) -> usize {
+        // Encode: owner || spender || value || nonce || deadline
+        out[..160].fill(0);
+        out[12..32].copy_from_slice(&self.owner);
+        out[44..64].copy_from_slice(&self.spender);
+        out[64..96].copy_from_slice(&self.value);
+        out[120..128].copy_from_slice(&self.nonce.to_be_bytes());
+        out[152..160].copy_from_slice(&self.deadline.to_be_bytes());
+        160
+    }
+}
 
-        // Encode data hash
-        encoded[96..128].copy_from_slice(&data_hash);
+/// Token payments to one payee, e.g. vesting installments, signed at once
+#[derive(Clone)]
+pub struct PaymentSchedule<'a> {
+    pub token: [u8; 20],
+    pub payee: [u8; 20],
+    /// Big-endian uint256 amounts, in payment order
+    pub amounts: &'a [[u8; 32]],
+    pub nonce: u64,
+}
 
-        // Encode nonce as uint256 (32 bytes, big-endian)
-        let nonce_bytes = self.nonce.to_be_bytes();
-        encoded[152..160].copy_from_slice(&nonce_bytes);
+impl Eip712Struct for PaymentSchedule<'_> {
+    fn type_hash() -> [u8; 32] {
+        keccak256(b"PaymentSchedule(address token,address payee,uint256[] amounts,uint256 nonce)")
+    }
 
-        keccak256(&encoded)
+    fn encode_data(&self, out: &mut [u8]) -> usize {
+        // Encode: token || payee || amountsHash || nonce
+        out[..128].fill(0);
+        out[12..32].copy_from_slice(&self.token);
+        out[44..64].copy_from_slice(&self.payee);
+        out[64..96].copy_from_slice(&encode_uint256_array(self.amounts));
+        out[120..128].copy_from_slice(&self.nonce.to_be_bytes());
+        128
     }
 }
 
 /// Compute EIP712 typed data hash
 pub fn eip712_hash(domain: &Eip712Domain, struct_hash: &[u8; 32]) -> [u8; 32] {
-    let domain_separator = domain.hash_struct();
+    typed_data_hash(&domain.hash_struct(), struct_hash)
+}
 
+/// Combine a domain separator and struct hash into the EIP712 digest
+fn typed_data_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
     // EIP712 message: "\x19\x01" || domainSeparator || structHash
-    let mut message = [0u8; 66];
-    message[0] = 0x19;
-    message[1] = 0x01;
-    message[2..34].copy_from_slice(&domain_separator);
-    message[34..66].copy_from_slice(struct_hash);
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(b"\x19\x01");
+    hasher.update(domain_separator);
+    hasher.update(struct_hash);
+    hasher.finalize()
+}
 
-    keccak256(&message)
+/// Compute the hash a Safe owner signs to approve a message
+///
+/// Matches `getMessageHashForSafe` in Safe's fallback handler for a 32-byte
+/// message: the inner hash is wrapped as `SafeMessage(bytes message)` under
+/// the Safe's own domain, which has only `chainId` and `verifyingContract`.
+pub fn safe_message_hash(
+    safe_address: &[u8; 20],
+    chain_id: u64,
+    message_hash: &[u8; 32],
+) -> [u8; 32] {
+    // Encode: typeHash || chainId || verifyingContract
+    let mut domain = [0u8; 96];
+    domain[0..32].copy_from_slice(&keccak256(
+        b"EIP712Domain(uint256 chainId,address verifyingContract)",
+    ));
+    domain[56..64].copy_from_slice(&chain_id.to_be_bytes());
+    domain[76..96].copy_from_slice(safe_address);
+
+    // Encode: typeHash || keccak256(message)
+    let mut safe_message = [0u8; 64];
+    safe_message[0..32].copy_from_slice(&keccak256(b"SafeMessage(bytes message)"));
+    safe_message[32..64].copy_from_slice(&keccak256(message_hash));
+
+    typed_data_hash(&keccak256(&domain), &keccak256(&safe_message))
+}
+
+/// Sign a message on behalf of a Safe the key owns
+///
+/// Returns a recoverable signature over `safe_message_hash`, ready to be
+/// packed as `r || s || v` for the Safe's `isValidSignature`.
+pub fn sign_safe_message(
+    keypair: &EthereumKeyPair,
+    safe_address: &[u8; 20],
+    chain_id: u64,
+    message_hash: &[u8; 32],
+) -> Result<(Signature, RecoveryId), ecdsa::Error> {
+    keypair.sign_hash_recoverable(&safe_message_hash(safe_address, chain_id, message_hash))
 }
 
 /// Sign EIP712 typed data
 pub fn sign_eip712(
     keypair: &EthereumKeyPair,
     domain: &Eip712Domain,
-    struct_hash: &[u8; 32],
+    message: &impl Eip712Struct,
 ) -> Signature {
-    let message_hash = eip712_hash(domain, struct_hash);
+    let message_hash = eip712_hash(domain, &message.hash_struct());
     keypair.signing_key.sign(&message_hash)
 }
 
@@ -309,9 +1000,144 @@ pub fn sign_eip712(
 pub fn verify_eip712(
     keypair: &EthereumKeyPair,
     domain: &Eip712Domain,
-    struct_hash: &[u8; 32],
+    message: &impl Eip712Struct,
     signature: &Signature,
 ) -> Result<(), ecdsa::Error> {
-    let message_hash = eip712_hash(domain, struct_hash);
+    let message_hash = eip712_hash(domain, &message.hash_struct());
     keypair.verifying_key.verify(&message_hash, signature)
 }
+
+/// Anti-rollback state: the highest transaction nonce accepted so far
+///
+/// Only the value needs persisting; store `last_seen()` in flash after
+/// every accepted transaction and restore it with `with_last_seen` at boot,
+/// or a reset reopens every old nonce.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NonceTracker {
+    last_seen: Option<u64>,
+}
+
+impl NonceTracker {
+    /// Tracker that has not accepted any nonce yet, so 0 is allowed
+    pub const fn new() -> Self {
+        NonceTracker { last_seen: None }
+    }
+
+    /// Tracker restored from a persisted `last_seen` value
+    pub const fn with_last_seen(last_seen: u64) -> Self {
+        NonceTracker {
+            last_seen: Some(last_seen),
+        }
+    }
+
+    /// Highest nonce accepted, or `None` before the first
+    pub fn last_seen(&self) -> Option<u64> {
+        self.last_seen
+    }
+
+    /// Accept `nonce` only if it is above every nonce accepted before
+    ///
+    /// Gaps are allowed, so skipping a nonce permanently voids it.
+    pub fn check_and_advance(&mut self, nonce: u64) -> Result<(), CryptoError> {
+        if self.last_seen.is_some_and(|last| nonce <= last) {
+            return Err(CryptoError::StaleNonce);
+        }
+        self.last_seen = Some(nonce);
+        Ok(())
+    }
+}
+
+/// M-of-N check of EIP712 signatures against a fixed set of signers
+pub struct MultisigVerifier {
+    /// Authorized signers, sorted and without duplicates
+    signers: Vec<[u8; 20]>,
+    threshold: usize,
+}
+
+impl MultisigVerifier {
+    /// Require `threshold` distinct signatures from `signers`
+    ///
+    /// Duplicate signer addresses are counted once.
+    pub fn new(signers: &[[u8; 20]], threshold: usize) -> Result<Self, CryptoError> {
+        let mut signers = signers.to_vec();
+        signers.sort_unstable();
+        signers.dedup();
+        if threshold == 0 || threshold > signers.len() {
+            return Err(CryptoError::InvalidThreshold);
+        }
+        Ok(MultisigVerifier { signers, threshold })
+    }
+
+    /// Authorized signers in ascending order
+    pub fn signers(&self) -> &[[u8; 20]] {
+        &self.signers
+    }
+
+    /// Number of distinct signatures required
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Recover the signer of each `r || s || v` signature over the EIP712 digest
+    ///
+    /// Every signature must come from a different authorized signer; any
+    /// invalid, unauthorized or repeated signature fails the whole check.
+    /// Returns the number of signers, which is at least the threshold.
+    pub fn verify_signatures(
+        &self,
+        domain: &Eip712Domain,
+        struct_hash: &[u8; 32],
+        sigs: &[[u8; 65]],
+    ) -> Result<usize, CryptoError> {
+        let digest = eip712_hash(domain, struct_hash);
+        let mut seen = alloc::vec![false; self.signers.len()];
+
+        for sig in sigs {
+            let signer = ecrecover(&digest, sig)?;
+            let index = self
+                .signer_index(&signer)
+                .ok_or(CryptoError::UnauthorizedSigner)?;
+            if core::mem::replace(&mut seen[index], true) {
+                return Err(CryptoError::DuplicateSigner);
+            }
+        }
+
+        if sigs.len() < self.threshold {
+            return Err(CryptoError::BelowThreshold);
+        }
+        Ok(sigs.len())
+    }
+
+    /// Position of `signer` in `signers`, found in constant time
+    ///
+    /// Compares against every entry with `addresses_equal` and selects the
+    /// match without branching, so timing does not reveal how far into the
+    /// list (or into the address) a recovered signer matched.
+    fn signer_index(&self, signer: &[u8; 20]) -> Option<usize> {
+        let mut found = Choice::from(0);
+        let mut index = 0u32;
+        for (i, candidate) in self.signers.iter().enumerate() {
+            let hit = Choice::from(addresses_equal(candidate, signer) as u8);
+            index.conditional_assign(&(i as u32), hit);
+            found |= hit;
+        }
+        bool::from(found).then_some(index as usize)
+    }
+
+    /// Verify signatures over a transaction and consume its nonce
+    ///
+    /// As `verify_signatures`, but a transaction whose nonce is not above
+    /// the tracker's last one is refused, so old signed transactions cannot
+    /// be replayed. The tracker only advances once the signatures pass.
+    pub fn verify_transaction(
+        &self,
+        domain: &Eip712Domain,
+        tx: &MultisigTransaction,
+        sigs: &[[u8; 65]],
+        nonces: &mut NonceTracker,
+    ) -> Result<usize, CryptoError> {
+        let signers = self.verify_signatures(domain, &tx.hash_struct(), sigs)?;
+        nonces.check_and_advance(tx.nonce)?;
+        Ok(signers)
+    }
+}
@@ -0,0 +1,99 @@
+//! ECDSA over NIST P-256 (secp256r1) with SHA-256
+//!
+//! The curve used by WebAuthn/FIDO2 authenticators and most hardware
+//! security keys. Unlike `crypto`, nothing here knows about Ethereum: keys
+//! are identified by their SEC1 public key, and messages are hashed with
+//! SHA-256.
+
+use p256::{
+    ecdsa::{signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey},
+    elliptic_curve::rand_core::{CryptoRng, RngCore},
+    NonZeroScalar, Scalar,
+};
+
+use super::crypto::CryptoError;
+
+/// P-256 key pair
+///
+/// The private scalar is scrubbed by `zeroize` and again when the key pair
+/// is dropped. Copies handed out by `private_key_bytes` are the caller's
+/// to scrub.
+pub struct P256KeyPair {
+    pub signing_key: SigningKey,
+    pub verifying_key: VerifyingKey,
+    public_key_compressed: [u8; 33],
+}
+
+impl P256KeyPair {
+    /// Generate a new key pair using the provided RNG
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::from_signing_key(SigningKey::random(rng))
+    }
+
+    /// Build a key pair from an existing signing key
+    pub fn from_signing_key(signing_key: SigningKey) -> Self {
+        let verifying_key = VerifyingKey::from(&signing_key);
+
+        let mut public_key_compressed = [0u8; 33];
+        public_key_compressed.copy_from_slice(verifying_key.to_encoded_point(true).as_bytes());
+
+        P256KeyPair {
+            signing_key,
+            verifying_key,
+            public_key_compressed,
+        }
+    }
+
+    /// Import a key pair from a 32-byte big-endian private key
+    pub fn from_private_key_bytes(bytes: &[u8; 32]) -> Result<Self, CryptoError> {
+        let signing_key =
+            SigningKey::from_bytes(bytes.into()).map_err(|_| CryptoError::InvalidPrivateKey)?;
+        Ok(Self::from_signing_key(signing_key))
+    }
+
+    /// Sign `sha256(message)` with an RFC6979 deterministic nonce
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.signing_key.sign(message)
+    }
+
+    /// Verify a signature over `sha256(message)`
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), ecdsa::Error> {
+        self.verifying_key.verify(message, signature)
+    }
+
+    /// Wipe the private key, e.g. once a credential is revoked
+    ///
+    /// Replaces the scalar with 1 and clears the cached SEC1 key, so the
+    /// revoked credential's public key is no longer reported alongside a
+    /// dummy secret.
+    pub fn zeroize(&mut self) {
+        self.signing_key = SigningKey::from(NonZeroScalar::new(Scalar::ONE).unwrap());
+        self.verifying_key = *self.signing_key.verifying_key();
+        self.public_key_compressed = [0; 33];
+    }
+
+    /// Get the private key bytes
+    pub fn private_key_bytes(&self) -> [u8; 32] {
+        self.signing_key.to_bytes().into()
+    }
+
+    /// Get the compressed SEC1 public key bytes
+    pub fn public_key_compressed_bytes(&self) -> [u8; 33] {
+        self.public_key_compressed
+    }
+
+    /// Get the uncompressed SEC1 public key (`0x04 || x || y`)
+    ///
+    /// WebAuthn's COSE key format carries `x` and `y` separately.
+    pub fn public_key_uncompressed_bytes(&self) -> [u8; 65] {
+        let mut out = [0u8; 65];
+        out.copy_from_slice(self.verifying_key.to_encoded_point(false).as_bytes());
+        out
+    }
+}
+
+impl Drop for P256KeyPair {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
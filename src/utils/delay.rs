@@ -0,0 +1,76 @@
+//! Blocking delays
+//!
+//! `TimerDelay` counts SysTick ticks at the core clock recorded when the
+//! RCC was frozen, so its timing holds whatever `board::init_clocks` chose.
+//! The demos get one through `DemoContext`.
+//!
+//! `Delay` needs no peripheral: it spins for a number of core cycles and
+//! assumes the core runs at `board::SYSCLK_HZ`. It suits code that cannot
+//! own SysTick, such as the button debounce.
+
+use cortex_m::peripheral::SYST;
+use stm32f4xx_hal::{
+    hal::delay::DelayNs,
+    rcc::Clocks,
+    timer::{SysDelay, SysTimerExt},
+};
+
+use crate::board::{CYCLES_PER_MS, SYSCLK_HZ};
+
+/// Approximate busy-loop delay implementing `DelayNs`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Delay;
+
+impl Delay {
+    /// Create a new busy-loop delay
+    pub const fn new() -> Self {
+        Delay
+    }
+}
+
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        let cycles = ns as u64 * SYSCLK_HZ as u64 / 1_000_000_000;
+        cortex_m::asm::delay(cycles as u32);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        // One millisecond at a time so long waits cannot overflow
+        for _ in 0..ms {
+            cortex_m::asm::delay(CYCLES_PER_MS);
+        }
+    }
+}
+
+/// SysTick delay timed from the frozen clocks
+pub struct TimerDelay {
+    inner: SysDelay,
+}
+
+impl TimerDelay {
+    /// Take over SysTick, counting at the core clock in `clocks`
+    pub fn new(syst: SYST, clocks: &Clocks) -> Self {
+        TimerDelay {
+            inner: syst.delay(clocks),
+        }
+    }
+
+    /// Give SysTick back
+    pub fn release(self) -> SYST {
+        self.inner.release().release()
+    }
+}
+
+impl DelayNs for TimerDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.inner.delay_ns(ns);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        // The HAL converts to SysTick ticks in u32, which overflows after
+        // about 51 s at 84 MHz, so wait one millisecond at a time
+        for _ in 0..ms {
+            self.inner.delay_ms(1);
+        }
+    }
+}
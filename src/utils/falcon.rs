@@ -4,9 +4,42 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 use miden_crypto::dsa::rpo_falcon512::{PublicKey, SecretKey, Signature};
+use miden_crypto::utils::{Deserializable, Serializable};
 use miden_crypto::{Felt, Word};
 
+/// Message bytes packed into each field element
+const MESSAGE_CHUNK: usize = 7;
+
+/// Errors returned when decoding Falcon512 keys and signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FalconError {
+    /// The public key bytes are not four canonical field elements
+    MalformedPublicKey,
+    /// The signature bytes are not a valid Falcon512 signature
+    MalformedSignature,
+}
+
+/// Milestones reported by `Falcon512KeyPair::generate_with_progress`
+///
+/// miden-crypto generates the secret key in a single call, so there is no
+/// progress to report inside `SamplingNtru`, which is where nearly all the
+/// time goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeygenStage {
+    /// Sampling the NTRU basis and building the secret key's LDL tree
+    SamplingNtru,
+    /// Deriving the public key from the secret key
+    ComputingPublicKey,
+    /// The key pair is ready
+    Finished,
+}
+
 /// Falcon512 key pair structure
+///
+/// The secret key is not scrubbed on drop: miden-crypto's `SecretKey` keeps
+/// its lattice basis and LDL tree in heap buffers that are only reachable
+/// through shared references and do not implement `Zeroize`. Keep the key
+/// pair alive for as short a time as possible.
 pub struct Falcon512KeyPair {
     pub secret_key: SecretKey,
     pub public_key: PublicKey,
@@ -15,9 +48,23 @@ pub struct Falcon512KeyPair {
 impl Falcon512KeyPair {
     /// Generate a new Falcon512 key pair using the provided RNG
     pub fn generate<R: rand::Rng>(rng: &mut R) -> Self {
+        Self::generate_with_progress(rng, |_| {})
+    }
+
+    /// Generate a key pair, calling `progress` as each stage begins
+    ///
+    /// Every stage in `KeygenStage` is reported once, in order.
+    pub fn generate_with_progress<R: rand::Rng, F: FnMut(KeygenStage)>(
+        rng: &mut R,
+        mut progress: F,
+    ) -> Self {
+        progress(KeygenStage::SamplingNtru);
         let secret_key = SecretKey::with_rng(rng);
+
+        progress(KeygenStage::ComputingPublicKey);
         let public_key = secret_key.public_key();
 
+        progress(KeygenStage::Finished);
         Falcon512KeyPair {
             secret_key,
             public_key,
@@ -28,38 +75,116 @@ impl Falcon512KeyPair {
     /// Message is hashed to a Word before signing
     pub fn sign<R: rand::Rng>(&self, message: &[u8], rng: &mut R) -> Signature {
         // Hash message to Word
-        let message_word = Self::hash_message_to_word(message);
+        let message_word = hash_bytes_to_word(message);
         self.secret_key.sign_with_rng(message_word, rng)
     }
 
     /// Verify a signature
     pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
-        let message_word = Self::hash_message_to_word(message);
+        let message_word = hash_bytes_to_word(message);
         self.public_key.verify(message_word, signature)
     }
 
+    /// Verify a signature received as bytes from `signature_to_bytes`
+    pub fn verify_bytes(&self, message: &[u8], sig_bytes: &[u8]) -> bool {
+        match signature_from_bytes(sig_bytes) {
+            Ok(signature) => self.verify(message, &signature),
+            Err(_) => false,
+        }
+    }
+
+    /// Serialize the public key (32 bytes, four little-endian field elements)
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key_word().to_bytes()
+    }
+
     /// Get the public key as a Word (4 field elements)
     pub fn public_key_word(&self) -> Word {
         self.public_key.into()
     }
 
-    /// Simple hash function to convert message bytes to Word
-    /// This is a basic implementation - in production you'd use a proper hash
-    fn hash_message_to_word(message: &[u8]) -> Word {
-        use miden_crypto::hash::rpo::Rpo256;
-
-        // Convert message bytes to field elements
-        let mut elements = Vec::new();
-        for chunk in message.chunks(8) {
-            let mut val: u64 = 0;
-            for (j, &byte) in chunk.iter().enumerate() {
-                val |= (byte as u64) << (j * 8);
-            }
-            elements.push(Felt::new(val));
-        }
+    /// Hash a message to the Word that Falcon512 signs (see `hash_bytes_to_word`)
+    pub fn hash_message_to_word(message: &[u8]) -> Word {
+        hash_bytes_to_word(message)
+    }
+}
+
+/// RPO-hash arbitrary bytes to a `Word`
+///
+/// The message length is absorbed as the first element, followed by the
+/// message packed 7 bytes per element (little-endian, so every element
+/// stays below the field modulus). A `0x01` byte and then zeros pad the
+/// final element; it is always added, even when the length is a
+/// multiple of 7. Distinct messages therefore map to distinct element
+/// sequences before hashing.
+///
+/// This is the message hash Falcon512 signs, and needs no key pair.
+pub fn hash_bytes_to_word(message: &[u8]) -> Word {
+    use miden_crypto::hash::rpo::Rpo256;
 
-        // Hash the elements and convert to Word
-        let digest = Rpo256::hash_elements(&elements);
-        digest.into()
+    let mut elements = Vec::with_capacity(2 + message.len() / MESSAGE_CHUNK);
+    elements.push(Felt::new(message.len() as u64));
+
+    let mut chunks = message.chunks_exact(MESSAGE_CHUNK);
+    for chunk in &mut chunks {
+        elements.push(Felt::new(pack_le(chunk)));
     }
+
+    let tail = chunks.remainder();
+    let padded = pack_le(tail) | 1 << (8 * tail.len());
+    elements.push(Felt::new(padded));
+
+    Rpo256::hash_elements(&elements).into()
+}
+
+/// Verify a signature against a public key received as a `Word`
+///
+/// This is the form `public_key_word()` emits, so a second device can check
+/// signatures without the signer's key pair.
+pub fn verify_with_word(public_key_word: Word, message: &[u8], signature: &Signature) -> bool {
+    let message_word = hash_bytes_to_word(message);
+    PublicKey::new(public_key_word).verify(message_word, signature)
+}
+
+/// RPO hash committing to a signature
+///
+/// Absorbs the nonce elements, then the coefficients of the signature
+/// polynomial `s2` and of the signer's public key polynomial `h`, so it is
+/// deterministic and changes with any part of the signature. This is a
+/// commitment only: a verifier cannot check it against a message, so keep
+/// the full signature wherever the signature itself must be verified.
+pub fn signature_commitment(sig: &Signature) -> Word {
+    use miden_crypto::hash::rpo::Rpo256;
+
+    let mut elements = Vec::with_capacity(8 + 2 * 512);
+    elements.extend_from_slice(&sig.nonce().to_elements());
+    elements.extend(sig.sig_poly().to_elements());
+    elements.extend(sig.pk_poly().to_elements());
+
+    Rpo256::hash_elements(&elements).into()
+}
+
+/// Serialize a signature in miden-crypto's wire format
+pub fn signature_to_bytes(sig: &Signature) -> Vec<u8> {
+    sig.to_bytes()
+}
+
+/// Parse a signature produced by `signature_to_bytes`
+pub fn signature_from_bytes(bytes: &[u8]) -> Result<Signature, FalconError> {
+    Signature::read_from_bytes(bytes).map_err(|_| FalconError::MalformedSignature)
+}
+
+/// Parse a public key produced by `Falcon512KeyPair::public_key_bytes`
+pub fn from_public_key_bytes(bytes: &[u8]) -> Result<PublicKey, FalconError> {
+    Word::read_from_bytes(bytes)
+        .map(PublicKey::new)
+        .map_err(|_| FalconError::MalformedPublicKey)
+}
+
+/// Pack up to 8 bytes into a u64, little-endian
+fn pack_le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &byte)| acc | (byte as u64) << (8 * i))
 }
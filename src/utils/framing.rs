@@ -0,0 +1,110 @@
+//! COBS (Consistent Overhead Byte Stuffing) framing for binary transports
+//!
+//! COBS rewrites a payload so it contains no zero bytes, which frees `0x00`
+//! to act as an unambiguous frame delimiter. Binary blobs such as proofs and
+//! signatures can then be sent over UART/RTT without any byte being mistaken
+//! for a terminator. Overhead is at most one byte per 254 payload bytes,
+//! plus the delimiter.
+
+/// Frame delimiter byte
+pub const DELIMITER: u8 = 0x00;
+
+/// Errors returned by frame encoding and decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramingError {
+    /// The output buffer is too small
+    OutputTooSmall,
+    /// The frame is not valid COBS
+    MalformedFrame,
+}
+
+/// Worst-case encoded size of a payload, including the delimiter
+pub const fn max_encoded_len(len: usize) -> usize {
+    len + len / 254 + 2
+}
+
+/// Encode a payload as a COBS frame terminated by `DELIMITER`
+///
+/// Returns the number of bytes written to `out`.
+pub fn encode_frame(data: &[u8], out: &mut [u8]) -> Result<usize, FramingError> {
+    if out.len() < max_encoded_len(data.len()) {
+        return Err(FramingError::OutputTooSmall);
+    }
+
+    let mut code_index = 0;
+    let mut write_index = 1;
+    let mut code: u8 = 1;
+
+    for &byte in data {
+        if byte == 0 {
+            out[code_index] = code;
+            code_index = write_index;
+            write_index += 1;
+            code = 1;
+        } else {
+            out[write_index] = byte;
+            write_index += 1;
+            code += 1;
+            if code == 0xFF {
+                // Maximum run length reached, start a new block
+                out[code_index] = code;
+                code_index = write_index;
+                write_index += 1;
+                code = 1;
+            }
+        }
+    }
+
+    out[code_index] = code;
+    out[write_index] = DELIMITER;
+    Ok(write_index + 1)
+}
+
+/// Decode a COBS frame back into the original payload
+///
+/// A trailing `DELIMITER` is accepted but not required. Returns the number
+/// of bytes written to `out`.
+pub fn decode_frame(frame: &[u8], out: &mut [u8]) -> Result<usize, FramingError> {
+    let frame = match frame.split_last() {
+        Some((&DELIMITER, rest)) => rest,
+        _ => frame,
+    };
+
+    let mut read_index = 0;
+    let mut write_index = 0;
+
+    while read_index < frame.len() {
+        let code = frame[read_index];
+        if code == 0 {
+            return Err(FramingError::MalformedFrame);
+        }
+        read_index += 1;
+
+        let run = code as usize - 1;
+        if read_index + run > frame.len() {
+            return Err(FramingError::MalformedFrame);
+        }
+        if write_index + run > out.len() {
+            return Err(FramingError::OutputTooSmall);
+        }
+        for &byte in &frame[read_index..read_index + run] {
+            if byte == 0 {
+                return Err(FramingError::MalformedFrame);
+            }
+            out[write_index] = byte;
+            write_index += 1;
+        }
+        read_index += run;
+
+        // Every block except a full one and the last implies a zero byte
+        if code != 0xFF && read_index < frame.len() {
+            if write_index >= out.len() {
+                return Err(FramingError::OutputTooSmall);
+            }
+            out[write_index] = 0;
+            write_index += 1;
+        }
+    }
+
+    Ok(write_index)
+}
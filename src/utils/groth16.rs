@@ -2,66 +2,405 @@
 //!
 //! This module provides a wrapper around arkworks Groth16 verification
 //! optimized for embedded systems (no_std).
+//!
+//! Keys, proofs and the verifiers are generic over the pairing engine and
+//! default to BN254 (circom/snarkjs). Any BN or BLS12 curve works, e.g.
+//! `ark_bls12_381::Bls12_381` (enable the `bls12-381` feature) for keys
+//! from gnark or arkworks stacks.
 
 #![allow(dead_code)]
 
-use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
-use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_std::vec::Vec;
-use core::ops::{AddAssign, Neg};
+use ark_bn254::{Bn254, Fr};
+use ark_ec::{
+    bls12::{Bls12, Bls12Parameters},
+    bn::{Bn, BnParameters},
+    short_weierstrass_jacobian::GroupAffine,
+    AffineCurve, PairingEngine, ProjectiveCurve, SWModelParameters,
+};
+use ark_ff::{One, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    vec::Vec,
+    Zero,
+};
+use core::ops::{AddAssign, MulAssign, Neg};
+
+use super::crypto::keccak256;
+use super::poseidon;
 
 /// Re-export the generated verification key and proof
 #[path = "vk_proof.rs"]
 pub mod vk_proof;
 
+/// Preimage `x` the sample proof was generated for (`circom/input.json`)
+pub const SAMPLE_PREIMAGE: u64 = 12345;
+
+/// Depth of the Merkle tree in `circom/imt_preimage.circom`
+pub const IMT_DEPTH: usize = 20;
+
+/// Root the sample proof's single public input should commit to
+///
+/// Recomputes the circuit's statement from the known preimage: the leaf is
+/// `Poseidon(x)` and, with every sibling zero and every path bit zero (as in
+/// `circom/input.json`), each level hashes `Poseidon(cur, 0)` up to the
+/// root. A mismatch with `sample_proof()` means the converter or the
+/// generated `public.json` is out of step with the circuit inputs.
+pub fn expected_public_input() -> Fr {
+    let mut cur = poseidon::hash1(Fr::from(SAMPLE_PREIMAGE));
+    for _ in 0..IMT_DEPTH {
+        cur = poseidon::hash2(cur, Fr::zero());
+    }
+    cur
+}
+
+/// Errors reported by Groth16 key and proof checks and by verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Groth16Error {
+    /// The number of public inputs does not match the verification key
+    InvalidPublicInputLength { expected: usize, got: usize },
+    /// The pairing equation does not hold: the proof is invalid for these
+    /// public inputs
+    PairingMismatch,
+    /// A curve point does not satisfy the curve equation
+    PointNotOnCurve,
+    /// A curve point is on the curve but outside the prime-order subgroup
+    PointNotInSubgroup,
+    /// The verification key has no IC elements
+    EmptyIc,
+    /// A byte encoding is truncated, has trailing bytes or holds an invalid
+    /// field element
+    Malformed,
+    /// A batch has a different number of proofs and public input sets
+    BatchSizeMismatch { proofs: usize, inputs: usize },
+}
+
+/// Pairing engines whose points can be checked before verifying
+///
+/// Implemented for every BN and BLS12 curve.
+pub trait Groth16Engine: PairingEngine {
+    /// Check that a G1 point is on the curve and in the prime-order subgroup
+    fn check_g1(point: &Self::G1Affine) -> Result<(), Groth16Error>;
+
+    /// Check that a G2 point is on the curve and in the prime-order subgroup
+    fn check_g2(point: &Self::G2Affine) -> Result<(), Groth16Error>;
+}
+
+impl<P: BnParameters> Groth16Engine for Bn<P> {
+    fn check_g1(point: &Self::G1Affine) -> Result<(), Groth16Error> {
+        check_point(point)
+    }
+
+    fn check_g2(point: &Self::G2Affine) -> Result<(), Groth16Error> {
+        check_point(point)
+    }
+}
+
+impl<P: Bls12Parameters> Groth16Engine for Bls12<P> {
+    fn check_g1(point: &Self::G1Affine) -> Result<(), Groth16Error> {
+        check_point(point)
+    }
+
+    fn check_g2(point: &Self::G2Affine) -> Result<(), Groth16Error> {
+        check_point(point)
+    }
+}
+
 /// Wrapper for Groth16 proof that matches our generated format
-pub struct Proof {
-    pub a: G1Projective,
-    pub b: G2Projective,
-    pub c: G1Projective,
+pub struct Proof<E: Groth16Engine = Bn254> {
+    pub a: E::G1Projective,
+    pub b: E::G2Projective,
+    pub c: E::G1Projective,
+}
+
+// Derived impls would needlessly require `E: Clone`
+impl<E: Groth16Engine> Clone for Proof<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Groth16Engine> Copy for Proof<E> {}
+
+impl<E: Groth16Engine> Proof<E> {
+    /// Decode a proof from arkworks' uncompressed encoding of `a`, `b`, `c`
+    ///
+    /// Every point is checked to be on the curve and in the prime-order
+    /// subgroup.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Groth16Error> {
+        let mut reader = bytes;
+        let a = read_g1::<E>(&mut reader)?;
+        let b = read_g2::<E>(&mut reader)?;
+        let c = read_g1::<E>(&mut reader)?;
+        finish(reader)?;
+
+        Ok(Proof {
+            a: a.into_projective(),
+            b: b.into_projective(),
+            c: c.into_projective(),
+        })
+    }
+
+    /// Encode the proof as `deserialize_uncompressed` expects
+    pub fn serialize_uncompressed(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Writing into a Vec cannot fail
+        let _ = self.a.into_affine().serialize_uncompressed(&mut bytes);
+        let _ = self.b.into_affine().serialize_uncompressed(&mut bytes);
+        let _ = self.c.into_affine().serialize_uncompressed(&mut bytes);
+        bytes
+    }
 }
 
 /// Wrapper for verification key
-pub struct Vk<'a> {
-    pub alpha_g1: G1Projective,
-    pub beta_g2: G2Projective,
-    pub gamma_g2: G2Projective,
-    pub delta_g2: G2Projective,
-    pub ic: &'a [G1Projective],
+pub struct Vk<'a, E: Groth16Engine = Bn254> {
+    pub alpha_g1: E::G1Projective,
+    pub beta_g2: E::G2Projective,
+    pub gamma_g2: E::G2Projective,
+    pub delta_g2: E::G2Projective,
+    pub ic: &'a [E::G1Projective],
 }
 
 /// Prepared verification key for faster verification
-pub struct PreparedVk {
-    pub alpha_g1: G1Affine,
-    pub beta_g2: G2Affine,
-    pub gamma_g2_neg: G2Affine,
-    pub delta_g2_neg: G2Affine,
-    pub gamma_abc_g1: Vec<G1Affine>,
-    pub e_alpha_beta: <Bn254 as PairingEngine>::Fqk,
+///
+/// Both the original and negated gamma/delta points are kept so alternative
+/// arrangements of the pairing equation can pick whichever they need.
+pub struct PreparedVk<E: Groth16Engine = Bn254> {
+    pub alpha_g1: E::G1Affine,
+    pub beta_g2: E::G2Affine,
+    pub gamma_g2: E::G2Affine,
+    pub delta_g2: E::G2Affine,
+    pub gamma_g2_neg: E::G2Affine,
+    pub delta_g2_neg: E::G2Affine,
+    pub gamma_abc_g1: Vec<E::G1Affine>,
+    pub e_alpha_beta: E::Fqk,
+}
+
+/// Verification key that owns its IC points, e.g. one received at runtime
+pub struct OwnedVk<E: Groth16Engine = Bn254> {
+    pub alpha_g1: E::G1Projective,
+    pub beta_g2: E::G2Projective,
+    pub gamma_g2: E::G2Projective,
+    pub delta_g2: E::G2Projective,
+    pub ic: Vec<E::G1Projective>,
+}
+
+impl<E: Groth16Engine> OwnedVk<E> {
+    /// Borrow as a `Vk` for preparing or verifying
+    pub fn as_vk(&self) -> Vk<'_, E> {
+        Vk {
+            alpha_g1: self.alpha_g1,
+            beta_g2: self.beta_g2,
+            gamma_g2: self.gamma_g2,
+            delta_g2: self.delta_g2,
+            ic: &self.ic,
+        }
+    }
 }
 
-impl<'a> Vk<'a> {
+impl<'a, E: Groth16Engine> Vk<'a, E> {
+    /// Decode a verification key from arkworks' uncompressed encoding
+    ///
+    /// Layout: alpha (G1), beta, gamma, delta (G2), then the IC points as a
+    /// u64 little-endian count followed by that many G1 points. Every point
+    /// is checked to be on the curve and in the prime-order subgroup.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<OwnedVk<E>, Groth16Error> {
+        let mut reader = bytes;
+        let alpha_g1 = read_g1::<E>(&mut reader)?;
+        let beta_g2 = read_g2::<E>(&mut reader)?;
+        let gamma_g2 = read_g2::<E>(&mut reader)?;
+        let delta_g2 = read_g2::<E>(&mut reader)?;
+
+        let count = u64::deserialize(&mut reader).map_err(|_| Groth16Error::Malformed)?;
+        let mut ic = Vec::new();
+        for _ in 0..count {
+            ic.push(read_g1::<E>(&mut reader)?.into_projective());
+        }
+        finish(reader)?;
+        if ic.is_empty() {
+            return Err(Groth16Error::EmptyIc);
+        }
+
+        Ok(OwnedVk {
+            alpha_g1: alpha_g1.into_projective(),
+            beta_g2: beta_g2.into_projective(),
+            gamma_g2: gamma_g2.into_projective(),
+            delta_g2: delta_g2.into_projective(),
+            ic,
+        })
+    }
+
+    /// Encode the key as `deserialize_uncompressed` expects
+    pub fn serialize_uncompressed(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Writing into a Vec cannot fail
+        let _ = self
+            .alpha_g1
+            .into_affine()
+            .serialize_uncompressed(&mut bytes);
+        let _ = self
+            .beta_g2
+            .into_affine()
+            .serialize_uncompressed(&mut bytes);
+        let _ = self
+            .gamma_g2
+            .into_affine()
+            .serialize_uncompressed(&mut bytes);
+        let _ = self
+            .delta_g2
+            .into_affine()
+            .serialize_uncompressed(&mut bytes);
+        let _ = (self.ic.len() as u64).serialize(&mut bytes);
+        for point in self.ic {
+            let _ = point.into_affine().serialize_uncompressed(&mut bytes);
+        }
+        bytes
+    }
+
     /// Prepare the verification key for faster verification
-    pub fn prepare(&self) -> PreparedVk {
+    pub fn prepare(&self) -> PreparedVk<E> {
         let alpha_affine = self.alpha_g1.into_affine();
         let beta_affine = self.beta_g2.into_affine();
+        let gamma_affine = self.gamma_g2.into_affine();
+        let delta_affine = self.delta_g2.into_affine();
 
         PreparedVk {
             alpha_g1: alpha_affine,
             beta_g2: beta_affine,
-            gamma_g2_neg: self.gamma_g2.into_affine().neg(),
-            delta_g2_neg: self.delta_g2.into_affine().neg(),
+            gamma_g2: gamma_affine,
+            delta_g2: delta_affine,
+            gamma_g2_neg: gamma_affine.neg(),
+            delta_g2_neg: delta_affine.neg(),
             gamma_abc_g1: self.ic.iter().map(|p| p.into_affine()).collect(),
-            e_alpha_beta: Bn254::pairing(alpha_affine, beta_affine),
+            e_alpha_beta: E::pairing(alpha_affine, beta_affine),
+        }
+    }
+}
+
+impl<E: Groth16Engine> PreparedVk<E> {
+    /// Check the internal consistency of the prepared verification key
+    ///
+    /// Verifies that every key point is on the curve and in the correct
+    /// subgroup, and that the IC list is non-empty. Intended to be called once
+    /// after `prepare` so a corrupted key is reported clearly instead of
+    /// showing up as unexplained verification failures.
+    pub fn validate(&self) -> Result<(), Groth16Error> {
+        if self.gamma_abc_g1.is_empty() {
+            return Err(Groth16Error::EmptyIc);
+        }
+
+        E::check_g1(&self.alpha_g1)?;
+        E::check_g2(&self.beta_g2)?;
+        E::check_g2(&self.gamma_g2)?;
+        E::check_g2(&self.delta_g2)?;
+        for point in &self.gamma_abc_g1 {
+            E::check_g1(point)?;
         }
+
+        Ok(())
+    }
+
+    /// Compute a stable identity digest of the verification key
+    ///
+    /// Hashes the canonical compressed encoding of alpha, beta, gamma, delta
+    /// and every IC point with Keccak256, so a host can confirm the device
+    /// has the expected circuit's key loaded.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut encoded = Vec::new();
+        // Writing into a Vec cannot fail
+        let _ = self.alpha_g1.serialize(&mut encoded);
+        let _ = self.beta_g2.serialize(&mut encoded);
+        let _ = self.gamma_g2.serialize(&mut encoded);
+        let _ = self.delta_g2.serialize(&mut encoded);
+        for point in &self.gamma_abc_g1 {
+            let _ = point.serialize(&mut encoded);
+        }
+
+        keccak256(&encoded)
+    }
+}
+
+/// Decode public inputs: a u64 little-endian count, then that many field
+/// elements (32 bytes each on BN254, little-endian, canonical)
+pub fn deserialize_public_inputs<F: PrimeField>(bytes: &[u8]) -> Result<Vec<F>, Groth16Error> {
+    let mut reader = bytes;
+    let inputs =
+        Vec::<F>::deserialize_uncompressed(&mut reader).map_err(|_| Groth16Error::Malformed)?;
+    finish(reader)?;
+    Ok(inputs)
+}
+
+/// Encode public inputs as `deserialize_public_inputs` expects
+pub fn serialize_public_inputs<F: PrimeField>(inputs: &[F]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Writing into a Vec cannot fail
+    let _ = (inputs.len() as u64).serialize(&mut bytes);
+    for input in inputs {
+        let _ = input.serialize_uncompressed(&mut bytes);
+    }
+    bytes
+}
+
+/// Read an uncompressed G1 point and check it
+fn read_g1<E: Groth16Engine>(reader: &mut &[u8]) -> Result<E::G1Affine, Groth16Error> {
+    let point = E::G1Affine::deserialize_unchecked(reader).map_err(|_| Groth16Error::Malformed)?;
+    E::check_g1(&point)?;
+    Ok(point)
+}
+
+/// Read an uncompressed G2 point and check it
+fn read_g2<E: Groth16Engine>(reader: &mut &[u8]) -> Result<E::G2Affine, Groth16Error> {
+    let point = E::G2Affine::deserialize_unchecked(reader).map_err(|_| Groth16Error::Malformed)?;
+    E::check_g2(&point)?;
+    Ok(point)
+}
+
+/// Reject bytes left over after a complete encoding
+fn finish(rest: &[u8]) -> Result<(), Groth16Error> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(Groth16Error::Malformed)
+    }
+}
+
+/// Check that a short Weierstrass point is on the curve and in the
+/// prime-order subgroup
+fn check_point<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<(), Groth16Error> {
+    if !point.is_on_curve() {
+        return Err(Groth16Error::PointNotOnCurve);
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Groth16Error::PointNotInSubgroup);
     }
+    Ok(())
+}
+
+/// Check that every proof point is on the curve and in the prime-order subgroup
+///
+/// Run by the verifiers before pairing, so proofs built directly from
+/// projective points (not via `Proof::deserialize_uncompressed`) are
+/// checked too.
+pub fn validate_proof<E: Groth16Engine>(proof: &Proof<E>) -> Result<(), Groth16Error> {
+    E::check_g1(&proof.a.into_affine())?;
+    E::check_g2(&proof.b.into_affine())?;
+    E::check_g1(&proof.c.into_affine())
 }
 
 /// Aggregate public inputs with IC elements
-fn aggregate_inputs(prep_vk: &PreparedVk, public_inputs: &[Fr]) -> G1Projective {
-    if (public_inputs.len() + 1) != prep_vk.gamma_abc_g1.len() {
-        // Return identity element
-        return prep_vk.gamma_abc_g1[0].into_projective();
+fn aggregate_inputs<E: Groth16Engine>(
+    prep_vk: &PreparedVk<E>,
+    public_inputs: &[E::Fr],
+) -> Result<E::G1Projective, Groth16Error> {
+    if prep_vk.gamma_abc_g1.is_empty() {
+        return Err(Groth16Error::EmptyIc);
+    }
+    let expected = prep_vk.gamma_abc_g1.len() - 1;
+    if public_inputs.len() != expected {
+        return Err(Groth16Error::InvalidPublicInputLength {
+            expected,
+            got: public_inputs.len(),
+        });
     }
 
     let mut g_ic = prep_vk.gamma_abc_g1[0].into_projective();
@@ -69,11 +408,10 @@ fn aggregate_inputs(prep_vk: &PreparedVk, public_inputs: &[Fr]) -> G1Projective
         .iter()
         .zip(prep_vk.gamma_abc_g1.iter().skip(1))
     {
-        use ark_ff::PrimeField;
         g_ic.add_assign(&ic_point.mul(input.into_repr()));
     }
 
-    g_ic
+    Ok(g_ic)
 }
 
 /// Verify a Groth16 proof
@@ -85,8 +423,14 @@ fn aggregate_inputs(prep_vk: &PreparedVk, public_inputs: &[Fr]) -> G1Projective
 ///
 /// # Returns
 /// * `Ok(())` if the proof is valid
-/// * `Err(())` if the proof is invalid
-pub fn verify_proof(vk: &Vk<'_>, proof: &Proof, public_inputs: &[Fr]) -> Result<(), ()> {
+/// * `Err(Groth16Error::PairingMismatch)` if the proof is invalid
+/// * Another `Groth16Error` if a proof point is invalid or the inputs do not
+///   fit the key
+pub fn verify_proof<E: Groth16Engine>(
+    vk: &Vk<'_, E>,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr],
+) -> Result<(), Groth16Error> {
     let pvk = vk.prepare();
     verify_proof_prepared(&pvk, proof, public_inputs)
 }
@@ -102,14 +446,18 @@ pub fn verify_proof(vk: &Vk<'_>, proof: &Proof, public_inputs: &[Fr]) -> Result<
 ///
 /// # Returns
 /// * `Ok(())` if the proof is valid
-/// * `Err(())` if the proof is invalid
-pub fn verify_proof_prepared(
-    pvk: &PreparedVk,
-    proof: &Proof,
-    public_inputs: &[Fr],
-) -> Result<(), ()> {
+/// * `Err(Groth16Error::PairingMismatch)` if the proof is invalid
+/// * Another `Groth16Error` if a proof point is invalid or the inputs do not
+///   fit the key
+pub fn verify_proof_prepared<E: Groth16Engine>(
+    pvk: &PreparedVk<E>,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr],
+) -> Result<(), Groth16Error> {
+    validate_proof(proof)?;
+
     // Aggregate public inputs
-    let g_ic = aggregate_inputs(pvk, public_inputs);
+    let g_ic = aggregate_inputs(pvk, public_inputs)?;
 
     // Convert proof to affine
     let proof_a = proof.a.into_affine();
@@ -124,12 +472,11 @@ pub fn verify_proof_prepared(
     // e(A, B) * e(g_ic, -gamma) * e(C, -delta) = e(alpha, beta)
 
     // Compute pairings
-    let e_a_b = Bn254::pairing(proof_a, proof_b);
-    let e_ic_gamma = Bn254::pairing(g_ic_affine, pvk.gamma_g2_neg);
-    let e_c_delta = Bn254::pairing(proof_c, pvk.delta_g2_neg);
+    let e_a_b = E::pairing(proof_a, proof_b);
+    let e_ic_gamma = E::pairing(g_ic_affine, pvk.gamma_g2_neg);
+    let e_c_delta = E::pairing(proof_c, pvk.delta_g2_neg);
 
     // Compute LHS: e(A, B) * e(g_ic, -gamma) * e(C, -delta)
-    use core::ops::MulAssign;
     let mut lhs = e_a_b;
     lhs.mul_assign(&e_ic_gamma);
     lhs.mul_assign(&e_c_delta);
@@ -138,6 +485,76 @@ pub fn verify_proof_prepared(
     if lhs == pvk.e_alpha_beta {
         Ok(())
     } else {
-        Err(())
+        Err(Groth16Error::PairingMismatch)
+    }
+}
+
+/// Verify several proofs against one key with a single final exponentiation
+///
+/// Each proof's equation is scaled by a random 128-bit scalar `r_i` and the
+/// results are multiplied together:
+///
+/// `prod e(r_i A_i, B_i) * e(sum r_i IC_i, -gamma) * e(sum r_i C_i, -delta)
+///  * e(-(sum r_i) alpha, beta) == 1`
+///
+/// That is N + 3 Miller loops and one final exponentiation, instead of 3N
+/// pairings for N separate checks. The Miller loops run one pair at a time,
+/// so heap use does not grow with the batch size. An invalid proof makes
+/// the batch fail except with probability about 2^-128, provided the
+/// prover cannot predict `rng`. A failed batch does not say which proof is
+/// bad; fall back to `verify_proof_prepared` to find it.
+pub fn verify_proofs_batch<E: Groth16Engine, R: RngCore + CryptoRng>(
+    pvk: &PreparedVk<E>,
+    proofs: &[Proof<E>],
+    inputs: &[&[E::Fr]],
+    rng: &mut R,
+) -> Result<(), Groth16Error> {
+    if proofs.len() != inputs.len() {
+        return Err(Groth16Error::BatchSizeMismatch {
+            proofs: proofs.len(),
+            inputs: inputs.len(),
+        });
+    }
+    if proofs.is_empty() {
+        return Ok(());
+    }
+
+    let mut product = E::Fqk::one();
+    let mut ic_sum = E::G1Projective::zero();
+    let mut c_sum = E::G1Projective::zero();
+    let mut r_sum = E::Fr::zero();
+
+    for (proof, public_inputs) in proofs.iter().zip(inputs) {
+        validate_proof(proof)?;
+        let g_ic = aggregate_inputs(pvk, public_inputs)?;
+
+        let r = E::Fr::from((rng.next_u64() as u128) << 64 | rng.next_u64() as u128);
+        let r_repr = r.into_repr();
+
+        product.mul_assign(&miller_loop::<E>(
+            proof.a.mul(r_repr).into_affine(),
+            proof.b.into_affine(),
+        ));
+        ic_sum.add_assign(&g_ic.mul(r_repr));
+        c_sum.add_assign(&proof.c.mul(r_repr));
+        r_sum.add_assign(&r);
+    }
+
+    product.mul_assign(&miller_loop::<E>(ic_sum.into_affine(), pvk.gamma_g2_neg));
+    product.mul_assign(&miller_loop::<E>(c_sum.into_affine(), pvk.delta_g2_neg));
+    product.mul_assign(&miller_loop::<E>(
+        pvk.alpha_g1.mul(r_sum.into_repr()).neg().into_affine(),
+        pvk.beta_g2,
+    ));
+
+    if E::final_exponentiation(&product).is_some_and(|result| result.is_one()) {
+        Ok(())
+    } else {
+        Err(Groth16Error::PairingMismatch)
     }
 }
+
+/// Miller loop of a single (G1, G2) pair, without the final exponentiation
+fn miller_loop<E: Groth16Engine>(g1: E::G1Affine, g2: E::G2Affine) -> E::Fqk {
+    E::miller_loop(core::iter::once(&(g1.into(), g2.into())))
+}
@@ -0,0 +1,92 @@
+//! On-board status LED and a shared blink vocabulary
+//!
+//! Pin connections:
+//! - LED → PC13 (active low on the BlackPill)
+//!
+//! Every demo reports outcomes through the same `BlinkPattern`s, so one
+//! visual language applies across all binaries:
+//!
+//! | Pattern   | Meaning                      | Shape                          |
+//! |-----------|------------------------------|--------------------------------|
+//! | `Success` | Operation succeeded          | 3 short blinks (100 ms)        |
+//! | `Failure` | Check failed (e.g. bad sig)  | 3 long blinks (500 ms)         |
+//! | `Working` | Busy, call repeatedly        | 1 brief flash per 500 ms       |
+//! | `Error`   | Unexpected error             | 10 rapid blinks (50 ms)        |
+
+use stm32f4xx_hal::{
+    gpio::{Output, Pin, PushPull},
+    hal::delay::DelayNs,
+};
+
+// Type alias for the on-board LED pin
+pub type LedPin = Pin<'C', 13, Output<PushPull>>;
+
+/// On-board status LED, hiding the active-low wiring
+pub struct StatusLed {
+    pin: LedPin,
+}
+
+impl StatusLed {
+    /// Take ownership of the LED pin and switch the LED off
+    pub fn new(pin: LedPin) -> Self {
+        let mut led = StatusLed { pin };
+        led.off();
+        led
+    }
+
+    /// Switch the LED on
+    pub fn on(&mut self) {
+        self.pin.set_low();
+    }
+
+    /// Switch the LED off
+    pub fn off(&mut self) {
+        self.pin.set_high();
+    }
+
+    /// Toggle the LED
+    pub fn toggle(&mut self) {
+        self.pin.toggle();
+    }
+
+    /// Whether the LED is currently lit
+    pub fn is_on(&self) -> bool {
+        self.pin.is_set_low()
+    }
+}
+
+/// Standard LED patterns for reporting status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlinkPattern {
+    /// 3 short blinks: 100 ms on, 100 ms off
+    Success,
+    /// 3 long blinks: 500 ms on, 500 ms off
+    Failure,
+    /// 1 brief flash: 50 ms on, 450 ms off; repeat while busy
+    Working,
+    /// 10 rapid blinks: 50 ms on, 50 ms off
+    Error,
+}
+
+impl BlinkPattern {
+    /// (blink count, on time in ms, off time in ms)
+    const fn timing(&self) -> (u32, u32, u32) {
+        match self {
+            BlinkPattern::Success => (3, 100, 100),
+            BlinkPattern::Failure => (3, 500, 500),
+            BlinkPattern::Working => (1, 50, 450),
+            BlinkPattern::Error => (10, 50, 50),
+        }
+    }
+
+    /// Play the pattern once, leaving the LED off
+    pub fn play(&self, led: &mut StatusLed, delay: &mut impl DelayNs) {
+        let (count, on_ms, off_ms) = self.timing();
+        for _ in 0..count {
+            led.on();
+            delay.delay_ms(on_ms);
+            led.off();
+            delay.delay_ms(off_ms);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Log output routed to RTT or a UART
+//!
+//! The backend is chosen at build time:
+//! - `rtt` (default): lines go to the RTT up-channel and need a probe attached
+//! - `uart`: lines go out on USART1, so a board can run headless with a
+//!   USB-UART adapter; takes precedence if both features are enabled
+//!
+//! Pin connections for the `uart` backend (USART1, 8N1):
+//! - TX → PA9
+//!
+//! Use the crate-level `log!` macro, which works like `rprintln!`. Under the
+//! `uart` backend, output before `init_uart` is silently dropped. Building
+//! with neither feature is a compile error.
+
+use core::fmt;
+
+#[cfg(not(any(feature = "rtt", feature = "uart")))]
+compile_error!("`log!` needs a backend: enable the `rtt` or `uart` feature");
+
+#[cfg(feature = "uart")]
+use core::{cell::RefCell, fmt::Write};
+#[cfg(feature = "uart")]
+use cortex_m::interrupt::{self, Mutex};
+#[cfg(feature = "uart")]
+use stm32f4xx_hal::{
+    gpio::PA9,
+    pac::USART1,
+    prelude::*,
+    rcc::Rcc,
+    serial::{SerialExt, Tx},
+};
+
+#[cfg(feature = "uart")]
+static TX: Mutex<RefCell<Option<Tx<USART1>>>> = Mutex::new(RefCell::new(None));
+
+/// Take over USART1 for log output at the given baud rate
+///
+/// Call once, early in `main`. If the baud rate cannot be configured the
+/// backend stays uninitialized and output is dropped.
+#[cfg(feature = "uart")]
+pub fn init_uart(tx_pin: PA9, usart: USART1, rcc: &mut Rcc, baud: u32) {
+    if let Ok(tx) = usart.tx(tx_pin, baud.bps(), rcc) {
+        interrupt::free(|cs| TX.borrow(cs).replace(Some(tx)));
+    }
+}
+
+/// Write one line to the configured backend
+pub fn write_line(line: &str) {
+    write_fmt(format_args!("{}", line));
+}
+
+/// Write formatted output followed by a line break
+pub fn write_fmt(args: fmt::Arguments) {
+    #[cfg(feature = "uart")]
+    interrupt::free(|cs| {
+        if let Some(tx) = TX.borrow(cs).borrow_mut().as_mut() {
+            // Terminals expect CRLF; a failed write has nowhere to be reported
+            let _ = tx.write_fmt(args);
+            let _ = tx.write_str("\r\n");
+        }
+    });
+
+    #[cfg(all(feature = "rtt", not(feature = "uart")))]
+    rtt_target::rprintln!("{}", args);
+}
+
+/// Print a line through the configured log backend
+///
+/// Accepts the same arguments as `rprintln!`.
+#[macro_export]
+macro_rules! log {
+    () => {
+        $crate::utils::logger::write_line("")
+    };
+    ($($arg:tt)*) => {
+        $crate::utils::logger::write_fmt(format_args!($($arg)*))
+    };
+}
@@ -4,10 +4,44 @@
 //! multiple binaries, including display drivers, crypto implementations,
 //! and other common functionality.
 
+pub mod abi;
+#[cfg(feature = "stm32f4")]
+pub mod bench;
+pub mod bip32;
+pub mod bip39;
+#[cfg(feature = "stm32f4")]
+pub mod button;
 pub mod crypto;
+pub mod crypto_p256;
+#[cfg(feature = "stm32f4")]
+pub mod delay;
 pub mod falcon;
+pub mod framing;
 pub mod groth16;
+#[cfg(feature = "stm32f4")]
+pub mod led;
+pub mod logger;
+pub mod mpt;
+#[cfg(feature = "stm32f4")]
 pub mod oled;
+pub mod poseidon;
+pub mod public_input;
+pub mod rlp;
+pub mod rng;
+#[cfg(feature = "stm32f4")]
+pub mod secure_link;
+pub mod stack;
+#[cfg(feature = "stm32f4")]
+pub mod timing;
+#[cfg(feature = "stm32f4")]
+pub mod transcript;
+pub mod tx;
+pub mod units;
+pub mod validator;
 
 // Re-export commonly used types for convenience
-pub use oled::{DcPin, OledDisplay, RstPin};
+#[cfg(feature = "stm32f4")]
+pub use oled::{
+    BitBangSpiInterface, DcPin, DisplaySize, DrawMode, EnablePin, I2cInterface, OledDisplay,
+    OledError, OledInterface, RstPin, ScrollSpeed, SpiInterface, TextConsole,
+};
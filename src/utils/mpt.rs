@@ -0,0 +1,168 @@
+//! Merkle Patricia trie inclusion proofs
+//!
+//! Verifies the proofs returned by `eth_getProof` against a state, storage,
+//! transaction or receipt root. The proof is the list of RLP-encoded nodes
+//! on the path from the root; each one must hash (Keccak256) to the
+//! reference held by its parent. Nodes shorter than 32 bytes are embedded
+//! in their parent instead of being referenced by hash.
+//!
+//! The key is the trie path itself. Ethereum's state and storage tries are
+//! "secure" tries, so pass `keccak256(address)` or `keccak256(slot)` there.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use super::crypto::keccak256;
+use super::rlp::{self, RlpError, RlpItem};
+
+/// Errors returned by proof verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MptError {
+    /// A node is not valid RLP
+    Rlp(RlpError),
+    /// A node is not a valid branch, extension or leaf
+    InvalidNode,
+    /// A node does not hash to the reference held by its parent
+    HashMismatch,
+    /// The proof ends before the path is resolved
+    MissingNode,
+}
+
+impl From<RlpError> for MptError {
+    fn from(e: RlpError) -> Self {
+        MptError::Rlp(e)
+    }
+}
+
+/// Reference from a parent node to a child
+enum NodeRef<'a> {
+    /// Keccak256 of the child, which must be the next proof node
+    Hash(&'a [u8]),
+    /// The child's list payload, embedded in the parent
+    Inline(&'a [u8]),
+}
+
+/// Verify a proof and return the value stored under `key`
+///
+/// Returns `Ok(None)` if the proof shows the key is absent. Any error means
+/// the proof itself is invalid and proves nothing.
+pub fn verify_mpt_proof(
+    root: &[u8; 32],
+    key: &[u8],
+    proof_nodes: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, MptError> {
+    let path_len = key.len() * 2;
+    let mut depth = 0; // Nibbles of the key consumed so far
+    let mut next_node = proof_nodes.iter();
+    let mut node_ref = NodeRef::Hash(root);
+
+    loop {
+        let payload = match node_ref {
+            NodeRef::Hash(hash) => {
+                let node = next_node.next().ok_or(MptError::MissingNode)?;
+                if keccak256(node)[..] != hash[..] {
+                    return Err(MptError::HashMismatch);
+                }
+                match rlp::decode(node)? {
+                    (RlpItem::List(payload), []) => payload,
+                    _ => return Err(MptError::InvalidNode),
+                }
+            }
+            NodeRef::Inline(payload) => payload,
+        };
+
+        let items = rlp::decode_list(payload)?;
+        match items.as_slice() {
+            // Branch: one child per nibble plus a value
+            [children @ .., RlpItem::Bytes(value)] if children.len() == 16 => {
+                if depth == path_len {
+                    return Ok(non_empty(value));
+                }
+                let child = &children[nibble(key, depth) as usize];
+                depth += 1;
+                match child_ref(child)? {
+                    Some(child) => node_ref = child,
+                    None => return Ok(None),
+                }
+            }
+            // Leaf or extension: hex-prefix encoded path plus value or child
+            [RlpItem::Bytes(encoded_path), second] => {
+                let (is_leaf, node_path) = decode_hex_prefix(encoded_path)?;
+                let matches = node_path.len() <= path_len - depth
+                    && node_path
+                        .iter()
+                        .enumerate()
+                        .all(|(i, &n)| nibble(key, depth + i) == n);
+
+                if is_leaf {
+                    return match second {
+                        RlpItem::Bytes(value) if matches && depth + node_path.len() == path_len => {
+                            Ok(Some(value.to_vec()))
+                        }
+                        RlpItem::Bytes(_) => Ok(None),
+                        RlpItem::List(_) => Err(MptError::InvalidNode),
+                    };
+                }
+
+                if !matches {
+                    return Ok(None);
+                }
+                depth += node_path.len();
+                node_ref = child_ref(second)?.ok_or(MptError::InvalidNode)?;
+            }
+            _ => return Err(MptError::InvalidNode),
+        }
+    }
+}
+
+/// Nibble `index` of `key`, high nibble first
+fn nibble(key: &[u8], index: usize) -> u8 {
+    let byte = key[index / 2];
+    if index.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    }
+}
+
+/// Interpret a branch or extension child; `None` for an empty slot
+fn child_ref<'a>(item: &RlpItem<'a>) -> Result<Option<NodeRef<'a>>, MptError> {
+    match *item {
+        RlpItem::Bytes([]) => Ok(None),
+        RlpItem::Bytes(hash) if hash.len() == 32 => Ok(Some(NodeRef::Hash(hash))),
+        RlpItem::List(payload) => Ok(Some(NodeRef::Inline(payload))),
+        RlpItem::Bytes(_) => Err(MptError::InvalidNode),
+    }
+}
+
+/// Decode a hex-prefix encoded path into the leaf flag and its nibbles
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(bool, Vec<u8>), MptError> {
+    let (&first, rest) = encoded.split_first().ok_or(MptError::InvalidNode)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(MptError::InvalidNode);
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        // Odd length: the first nibble shares the flag byte
+        nibbles.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return Err(MptError::InvalidNode);
+    }
+    for &byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+
+    Ok((flag & 2 == 2, nibbles))
+}
+
+/// Treat an empty branch value as absent
+fn non_empty(value: &[u8]) -> Option<Vec<u8>> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_vec())
+    }
+}
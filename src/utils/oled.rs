@@ -8,54 +8,655 @@
 //! - CS → PA4 (GPIO) - NOT USED, tied to GND
 //! - DC → PB0 (GPIO)
 //! - RES → PB1 (GPIO)
+//! - EN/BL → any GPIO (optional, only on modules with an enable or
+//!   backlight pin; see `with_enable_pin`)
+//!
+//! I2C-only modules connect SCL/SDA to an I2C peripheral instead (e.g.
+//! PB6/PB7 for I2C1) and are driven through `I2cInterface`. When SPI1's
+//! pins are taken, `BitBangSpiInterface` drives D0/D1/DC from any GPIOs.
 
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
 use stm32f4xx_hal::{
-    gpio::{Output, Pin, PushPull},
+    gpio::{alt::spi1::Miso, AnyPin, Output, Pin, PushPull, Speed, PA5, PA7, PB0, PB1},
+    hal::digital::OutputPin,
+    i2c::{I2c, Instance as I2cInstance},
     pac::SPI1,
-    spi::Spi,
+    prelude::*,
+    rcc::Rcc,
+    spi::{Mode, Phase, Polarity, Spi},
 };
 
 // Type aliases for our specific pin configuration
 pub type DcPin = Pin<'B', 0, Output<PushPull>>;
 pub type RstPin = Pin<'B', 1, Output<PushPull>>;
+/// Default enable/backlight pin type; any GPIO output after `.erase()`
+///
+/// `with_enable_pin` takes any `OutputPin`, e.g. an I/O expander line or
+/// an inverting wrapper, and changes the display's `EN` type to match.
+pub type EnablePin = AnyPin<Output<PushPull>>;
 
-/// Minimal SSD1306 OLED display driver
-pub struct OledDisplay {
+/// Width of the largest supported panel in pixels
+pub const WIDTH: usize = 128;
+/// Height of the largest supported panel in pixels
+pub const HEIGHT: usize = 64;
+/// Number of 8-pixel-high pages on the largest supported panel
+const PAGES: usize = HEIGHT / 8;
+
+/// Default number of bytes sent per bus transaction
+///
+/// Large transfers such as a full 1024-byte frame are split into pieces of
+/// this size. Smaller chunks keep any staging or DMA buffer small at the
+/// cost of more transactions; larger chunks mean fewer, longer ones.
+pub const DEFAULT_SPI_CHUNK: usize = 128;
+
+/// SPI clock used by `new_default`; the SSD1306 allows at most 10 MHz
+///
+/// SPI1 divides its 84 MHz bus clock by 16 for this, giving 5.25 MHz.
+pub const SPI_FREQ_HZ: u32 = 5_000_000;
+
+/// Attempts per display operation before giving up with a bus error
+const WRITE_ATTEMPTS: usize = 3;
+
+/// Upper bound on the points in one octant of a midpoint circle (r <= 255)
+const MAX_OCTANT_POINTS: usize = 184;
+
+/// Glyph drawn for characters missing from the font
+const BOX_GLYPH: [u8; 5] = [0x7F, 0x41, 0x41, 0x41, 0x7F];
+
+/// 5x7 font for printable ASCII (0x20-0x7E), one byte per column, LSB at the top
+static FONT5X7: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '\''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x42, 0x41, 0x51, 0x69, 0x46], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x00, 0x08, 0x14, 0x22, 0x41], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x41, 0x22, 0x14, 0x08, 0x00], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // 'F'
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x32, 0x49, 0x49, 0x49, 0x26], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x03, 0x04, 0x78, 0x04, 0x03], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x00, 0x7F, 0x41, 0x41], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x41, 0x41, 0x7F, 0x00, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x08, 0x14, 0x54, 0x54, 0x3C], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x00, 0x7F, 0x10, 0x28, 0x44], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+/// Status bar indicator glyphs
+const CHECK_GLYPH: [u8; 5] = [0x10, 0x20, 0x10, 0x08, 0x04];
+const CROSS_GLYPH: [u8; 5] = [0x22, 0x14, 0x08, 0x14, 0x22];
+
+/// Errors reported by the display driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OledError {
+    /// An SPI write to the panel failed
+    Spi,
+    /// An I2C write to the panel failed (e.g. no acknowledge)
+    I2c,
+    /// The enable/backlight pin could not be driven
+    Enable,
+}
+
+/// Default 7-bit address of SSD1306 I2C modules (0x3D with SA0 tied high)
+pub const DEFAULT_I2C_ADDRESS: u8 = 0x3C;
+
+/// Transport carrying commands and display RAM data to the controller
+pub trait OledInterface {
+    /// Send one command byte
+    fn command(&mut self, cmd: u8) -> Result<(), OledError>;
+
+    /// Send display RAM data
+    fn data(&mut self, buf: &[u8]) -> Result<(), OledError>;
+
+    /// Clear any latched bus error before an operation is retried
+    fn recover(&mut self) {}
+}
+
+/// 4-wire SPI transport: SPI1 plus a data/command select pin
+pub struct SpiInterface {
     spi: Spi<SPI1>,
     dc: DcPin,
 }
 
-impl OledDisplay {
-    /// Initialize the OLED display
-    pub fn new(spi: Spi<SPI1>, dc: DcPin, mut rst: RstPin) -> Result<Self, &'static str> {
-        let mut display = Self { spi, dc };
+impl SpiInterface {
+    /// Wrap the SPI bus and DC pin
+    pub fn new(spi: Spi<SPI1>, dc: DcPin) -> Self {
+        SpiInterface { spi, dc }
+    }
+}
+
+impl OledInterface for SpiInterface {
+    fn command(&mut self, cmd: u8) -> Result<(), OledError> {
+        self.dc.set_low(); // Command mode
+        self.spi.write(&[cmd]).map_err(|_| OledError::Spi)
+    }
+
+    fn data(&mut self, buf: &[u8]) -> Result<(), OledError> {
+        self.dc.set_high(); // Data mode
+        self.spi.write(buf).map_err(|_| OledError::Spi)
+    }
+
+    /// Clear a pending overrun so the next transfer starts cleanly
+    ///
+    /// The HAL reports OVR from the receive side without clearing it; the
+    /// flag clears on a DR read followed by an SR read.
+    fn recover(&mut self) {
+        // SAFETY: only reads, and this interface owns SPI1 so no transfer is in flight
+        let spi = unsafe { &*SPI1::ptr() };
+        let _ = spi.dr().read();
+        let _ = spi.sr().read();
+    }
+}
+
+/// I2C transport: each write starts with a control byte selecting
+/// command (`0x00`) or data (`0x40`)
+pub struct I2cInterface<I2C: I2cInstance> {
+    i2c: I2c<I2C>,
+    address: u8,
+}
+
+impl<I2C: I2cInstance> I2cInterface<I2C> {
+    /// Wrap the I2C bus for a panel at the given 7-bit address
+    pub fn new(i2c: I2c<I2C>, address: u8) -> Self {
+        I2cInterface { i2c, address }
+    }
+}
+
+impl<I2C: I2cInstance> OledInterface for I2cInterface<I2C> {
+    fn command(&mut self, cmd: u8) -> Result<(), OledError> {
+        self.i2c
+            .write(self.address, &[0x00, cmd])
+            .map_err(|_| OledError::I2c)
+    }
+
+    fn data(&mut self, buf: &[u8]) -> Result<(), OledError> {
+        let bytes = core::iter::once(0x40).chain(buf.iter().copied());
+        self.i2c
+            .write_iter(self.address, bytes)
+            .map_err(|_| OledError::I2c)
+    }
+}
+
+/// 4-wire SPI transport bit-banged on any three output pins
+///
+/// Sends MSB first in SPI mode 0: data changes while SCK is low and the
+/// panel samples it on the rising edge. Each clock half lasts at least
+/// `half_period_cycles` core cycles on top of the pin toggling itself; the
+/// SSD1306 needs 50 ns per half (a 10 MHz clock), so 0 is already safe
+/// at 84 MHz on short wires.
+pub struct BitBangSpiInterface<Sck, Mosi, Dc> {
+    sck: Sck,
+    mosi: Mosi,
+    dc: Dc,
+    half_period_cycles: u32,
+}
+
+impl<Sck: OutputPin, Mosi: OutputPin, Dc: OutputPin> BitBangSpiInterface<Sck, Mosi, Dc> {
+    /// Take the clock, data and data/command pins; SCK is driven low (idle)
+    pub fn new(mut sck: Sck, mosi: Mosi, dc: Dc, half_period_cycles: u32) -> Self {
+        let _ = sck.set_low();
+        BitBangSpiInterface {
+            sck,
+            mosi,
+            dc,
+            half_period_cycles,
+        }
+    }
+
+    /// Clock out one byte, MSB first
+    fn write_byte(&mut self, byte: u8) -> Result<(), OledError> {
+        for bit in (0..8).rev() {
+            let high = byte & (1 << bit) != 0;
+            self.mosi
+                .set_state(high.into())
+                .map_err(|_| OledError::Spi)?;
+            cortex_m::asm::delay(self.half_period_cycles);
+            self.sck.set_high().map_err(|_| OledError::Spi)?;
+            cortex_m::asm::delay(self.half_period_cycles);
+            self.sck.set_low().map_err(|_| OledError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Sck: OutputPin, Mosi: OutputPin, Dc: OutputPin> OledInterface
+    for BitBangSpiInterface<Sck, Mosi, Dc>
+{
+    fn command(&mut self, cmd: u8) -> Result<(), OledError> {
+        self.dc.set_low().map_err(|_| OledError::Spi)?; // Command mode
+        self.write_byte(cmd)
+    }
+
+    fn data(&mut self, buf: &[u8]) -> Result<(), OledError> {
+        self.dc.set_high().map_err(|_| OledError::Spi)?; // Data mode
+        buf.iter().try_for_each(|&byte| self.write_byte(byte))
+    }
+}
+
+/// Supported SSD1306 panel geometries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplaySize {
+    /// 128x64, the common 0.96" module
+    #[default]
+    Display128x64,
+    /// 128x32, the 0.91" strip module
+    Display128x32,
+    /// 64x48, the 0.66" module
+    Display64x48,
+}
+
+impl DisplaySize {
+    /// Width in pixels
+    pub const fn width(self) -> usize {
+        match self {
+            DisplaySize::Display128x64 | DisplaySize::Display128x32 => 128,
+            DisplaySize::Display64x48 => 64,
+        }
+    }
+
+    /// Height in pixels
+    pub const fn height(self) -> usize {
+        match self {
+            DisplaySize::Display128x64 => 64,
+            DisplaySize::Display128x32 => 32,
+            DisplaySize::Display64x48 => 48,
+        }
+    }
+
+    /// Number of 8-pixel-high pages
+    pub const fn pages(self) -> usize {
+        self.height() / 8
+    }
+
+    /// COM pins hardware configuration (sequential on 128x32, alternative otherwise)
+    const fn com_pins(self) -> u8 {
+        match self {
+            DisplaySize::Display128x32 => 0x02,
+            _ => 0x12,
+        }
+    }
+
+    /// First controller column wired to the panel
+    ///
+    /// The controller always has 128 columns; a 64-wide glass is connected
+    /// to the middle ones.
+    const fn column_offset(self) -> u8 {
+        match self {
+            DisplaySize::Display64x48 => 32,
+            _ => 0,
+        }
+    }
+}
+
+/// How drawn pixels combine with the framebuffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawMode {
+    /// Turn pixels on (OR)
+    Set,
+    /// Turn pixels off, e.g. dark text on a filled bar
+    Clear,
+    /// Invert pixels, e.g. a blinking cursor or selection highlight
+    Xor,
+}
+
+/// Interval between hardware scroll steps, in frames
+///
+/// At the SSD1306's default frame rate of roughly 100 Hz, `Frames2` moves
+/// one column about every 20 ms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollSpeed {
+    Frames2,
+    Frames3,
+    Frames4,
+    Frames5,
+    Frames25,
+    Frames64,
+    Frames128,
+    Frames256,
+}
+
+impl ScrollSpeed {
+    /// The 3-bit interval code used by the scroll setup commands
+    const fn code(self) -> u8 {
+        match self {
+            ScrollSpeed::Frames5 => 0b000,
+            ScrollSpeed::Frames64 => 0b001,
+            ScrollSpeed::Frames128 => 0b010,
+            ScrollSpeed::Frames256 => 0b011,
+            ScrollSpeed::Frames3 => 0b100,
+            ScrollSpeed::Frames4 => 0b101,
+            ScrollSpeed::Frames25 => 0b110,
+            ScrollSpeed::Frames2 => 0b111,
+        }
+    }
+}
+
+/// Minimal SSD1306 OLED display driver
+///
+/// All drawing goes into an in-RAM framebuffer; call `flush()` to push
+/// it to the panel. The display is also an embedded-graphics `DrawTarget`,
+/// so its fonts, shapes and images draw into the same framebuffer (use
+/// `DrawTarget::clear(&mut display, color)` to fill it, since the inherent
+/// `clear()` takes no color).
+///
+/// Generic over the bus and the optional enable pin; the defaults are the
+/// SPI wiring above and a HAL GPIO.
+pub struct OledDisplay<DI = SpiInterface, EN = EnablePin> {
+    interface: DI,
+    enable: Option<EN>,
+    size: DisplaySize,
+    /// Page-major pixels; only the first `width * pages` bytes are used
+    buffer: [u8; WIDTH * PAGES],
+    chunk_size: usize,
+}
 
+impl OledDisplay<SpiInterface> {
+    /// Reset and initialize an SPI OLED display of the given size
+    pub fn new(
+        spi: Spi<SPI1>,
+        dc: DcPin,
+        mut rst: RstPin,
+        size: DisplaySize,
+    ) -> Result<Self, OledError> {
         // Hardware reset
         rst.set_low();
         cortex_m::asm::delay(100_000); // ~5ms
         rst.set_high();
         cortex_m::asm::delay(100_000); // ~5ms
 
+        Self::with_interface(SpiInterface::new(spi, dc), size)
+    }
+
+    /// Set up SPI1 and the documented pins, then reset and initialize
+    ///
+    /// Configures PA5/PA7 as SPI1 SCK/MOSI in mode 0 at `SPI_FREQ_HZ` and
+    /// PB0/PB1 as the DC and RES outputs. MISO is not connected.
+    pub fn new_default(
+        spi1: SPI1,
+        sck: PA5,
+        mosi: PA7,
+        dc: PB0,
+        rst: PB1,
+        size: DisplaySize,
+        rcc: &mut Rcc,
+    ) -> Result<Self, OledError> {
+        let mode = Mode {
+            polarity: Polarity::IdleLow,
+            phase: Phase::CaptureOnFirstTransition,
+        };
+        let spi = Spi::new(
+            spi1,
+            (
+                Some(sck.into_alternate().speed(Speed::VeryHigh)),
+                None::<Miso>,
+                Some(mosi.into_alternate().speed(Speed::VeryHigh)),
+            ),
+            mode,
+            SPI_FREQ_HZ.Hz(),
+            rcc,
+        );
+
+        Self::new(
+            spi,
+            dc.into_push_pull_output(),
+            rst.into_push_pull_output(),
+            size,
+        )
+    }
+}
+
+impl<DI: OledInterface> OledDisplay<DI> {
+    /// Initialize a display of the given size over any interface
+    ///
+    /// No hardware reset is performed; I2C modules usually reset on power-up.
+    pub fn with_interface(interface: DI, size: DisplaySize) -> Result<Self, OledError> {
+        let mut display = Self {
+            interface,
+            enable: None,
+            size,
+            buffer: [0; WIDTH * PAGES],
+            chunk_size: DEFAULT_SPI_CHUNK,
+        };
+
         // Initialize SSD1306
-        display.init()?;
+        display.with_retry(Self::init)?;
 
         Ok(display)
     }
+}
+
+impl<DI: OledInterface, EN: OutputPin> OledDisplay<DI, EN> {
+    /// Attach an active-high enable/backlight pin and switch it on
+    ///
+    /// Any `OutputPin` works; an active-low pin can be wrapped in one that
+    /// inverts the level.
+    pub fn with_enable_pin<P: OutputPin>(
+        self,
+        mut enable: P,
+    ) -> Result<OledDisplay<DI, P>, OledError> {
+        enable.set_high().map_err(|_| OledError::Enable)?;
+        Ok(OledDisplay {
+            interface: self.interface,
+            enable: Some(enable),
+            size: self.size,
+            buffer: self.buffer,
+            chunk_size: self.chunk_size,
+        })
+    }
+
+    /// Drive the enable/backlight pin, if the module has one
+    pub fn set_enabled(&mut self, enabled: bool) -> Result<(), OledError> {
+        match self.enable.as_mut() {
+            Some(enable) => enable
+                .set_state(enabled.into())
+                .map_err(|_| OledError::Enable),
+            None => Ok(()),
+        }
+    }
+
+    /// Set the panel contrast (`0x7F` after init)
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), OledError> {
+        self.write_cmd(0x81)?;
+        self.write_cmd(level)
+    }
+
+    /// Invert every pixel on the panel without touching the framebuffer
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), OledError> {
+        self.write_cmd(if invert { 0xA7 } else { 0xA6 })
+    }
+
+    /// Switch the panel on or off (sleep mode)
+    ///
+    /// While off the panel draws almost no current but keeps its RAM, so it
+    /// shows the same image when switched back on.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), OledError> {
+        self.write_cmd(if on { 0xAF } else { 0xAE })
+    }
+
+    /// Continuously scroll pages `start_page..=end_page` to the right
+    ///
+    /// Content wraps around the panel edge. Stop the scroll before the next
+    /// `flush()`: the datasheet does not guarantee RAM writes while scrolling.
+    /// `end_page` is clamped to the panel; an empty range only stops scrolling.
+    pub fn start_scroll_right(
+        &mut self,
+        start_page: u8,
+        end_page: u8,
+        speed: ScrollSpeed,
+    ) -> Result<(), OledError> {
+        self.start_scroll(0x26, start_page, end_page, speed)
+    }
+
+    /// Continuously scroll pages `start_page..=end_page` to the left
+    ///
+    /// See `start_scroll_right`.
+    pub fn start_scroll_left(
+        &mut self,
+        start_page: u8,
+        end_page: u8,
+        speed: ScrollSpeed,
+    ) -> Result<(), OledError> {
+        self.start_scroll(0x27, start_page, end_page, speed)
+    }
+
+    /// Stop any hardware scroll
+    ///
+    /// The panel keeps the scrolled image; `flush()` redraws the framebuffer.
+    pub fn stop_scroll(&mut self) -> Result<(), OledError> {
+        self.write_cmd(0x2E)
+    }
+
+    /// Set up and activate a horizontal scroll (`0x26` right, `0x27` left)
+    fn start_scroll(
+        &mut self,
+        direction: u8,
+        start_page: u8,
+        end_page: u8,
+        speed: ScrollSpeed,
+    ) -> Result<(), OledError> {
+        // A new scroll setup is only accepted while scrolling is off
+        self.stop_scroll()?;
+
+        let end_page = end_page.min((self.size.pages() - 1) as u8);
+        if start_page > end_page {
+            return Ok(());
+        }
+
+        self.write_cmd(direction)?;
+        self.write_cmd(0x00)?; // Dummy byte
+        self.write_cmd(start_page)?;
+        self.write_cmd(speed.code())?;
+        self.write_cmd(end_page)?;
+        self.write_cmd(0x00)?; // Dummy bytes
+        self.write_cmd(0xFF)?;
+
+        // Activate scroll
+        self.write_cmd(0x2F)
+    }
 
     /// Send a command byte
-    fn write_cmd(&mut self, cmd: u8) -> Result<(), &'static str> {
-        self.dc.set_low(); // Command mode
-        self.spi.write(&[cmd]).map_err(|_| "SPI write failed")
+    fn write_cmd(&mut self, cmd: u8) -> Result<(), OledError> {
+        self.interface.command(cmd)
     }
 
-    /// Send data bytes
-    fn write_data(&mut self, data: &[u8]) -> Result<(), &'static str> {
-        self.dc.set_high(); // Data mode
-        self.spi.write(data).map_err(|_| "SPI write failed")
+    /// Run a display operation, retrying it after a bus error
+    ///
+    /// Single bytes are not retried: after a failed write it is unknown
+    /// whether the panel latched the byte, so resending could shift a
+    /// command's parameters or the RAM write pointer. `op` must instead be
+    /// safe to repeat from the start (the init sequence, or a flush that
+    /// begins by setting its address window). Every write selects command
+    /// or data mode itself, so each attempt starts in the right mode.
+    fn with_retry<F>(&mut self, mut op: F) -> Result<(), OledError>
+    where
+        F: FnMut(&mut Self) -> Result<(), OledError>,
+    {
+        let mut result = Err(OledError::Spi);
+        for _ in 0..WRITE_ATTEMPTS {
+            result = op(self);
+            if result.is_ok() {
+                break;
+            }
+            self.interface.recover();
+        }
+        result
+    }
+
+    /// Set the maximum number of bytes sent per SPI transaction
+    ///
+    /// See `DEFAULT_SPI_CHUNK` for the memory/throughput tradeoff. A size of
+    /// zero is treated as one byte.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
     }
 
     /// Initialize the display with SSD1306 commands
-    fn init(&mut self) -> Result<(), &'static str> {
+    fn init(&mut self) -> Result<(), OledError> {
         // Display off
         self.write_cmd(0xAE)?;
 
@@ -65,7 +666,7 @@ impl OledDisplay {
 
         // Set multiplex ratio
         self.write_cmd(0xA8)?;
-        self.write_cmd(0x3F)?; // 1/64 duty
+        self.write_cmd((self.size.height() - 1) as u8)?; // 1/height duty
 
         // Set display offset
         self.write_cmd(0xD3)?;
@@ -90,7 +691,7 @@ impl OledDisplay {
 
         // Set COM pins hardware configuration
         self.write_cmd(0xDA)?;
-        self.write_cmd(0x12)?;
+        self.write_cmd(self.size.com_pins())?;
 
         // Set contrast control
         self.write_cmd(0x81)?;
@@ -119,75 +720,193 @@ impl OledDisplay {
         Ok(())
     }
 
-    /// Clear the entire display
-    pub fn clear(&mut self) -> Result<(), &'static str> {
-        // Set column address range (0-127)
+    /// Set the column (`x0..=x1`) and page (`p0..=p1`) window for data writes
+    fn set_addr_window(&mut self, x0: u8, x1: u8, p0: u8, p1: u8) -> Result<(), OledError> {
+        let offset = self.size.column_offset();
+
+        // Set column address range
         self.write_cmd(0x21)?;
-        self.write_cmd(0)?;
-        self.write_cmd(127)?;
+        self.write_cmd(x0 + offset)?;
+        self.write_cmd(x1 + offset)?;
 
-        // Set page address range (0-7)
+        // Set page address range
         self.write_cmd(0x22)?;
-        self.write_cmd(0)?;
-        self.write_cmd(7)?;
+        self.write_cmd(p0)?;
+        self.write_cmd(p1)?;
+
+        Ok(())
+    }
 
-        // Write zeros to all pixels (128 columns * 8 pages = 1024 bytes)
-        let zeros = [0u8; 128];
-        for _ in 0..8 {
-            self.write_data(&zeros)?;
+    /// Push the whole framebuffer to the display
+    ///
+    /// A transient bus error restarts the frame, up to `WRITE_ATTEMPTS` times.
+    pub fn flush(&mut self) -> Result<(), OledError> {
+        let x1 = (self.size.width() - 1) as u8;
+        let p1 = (self.size.pages() - 1) as u8;
+        self.with_retry(|display| display.flush_window(0, x1, 0, p1))
+    }
+
+    /// Push only the pixels in `(x0, y0)..=(x1, y1)` to the display
+    ///
+    /// The panel is written in whole 8-pixel pages, so the rows are widened
+    /// to page boundaries. Coordinates past the edge are clamped; an empty
+    /// region sends nothing.
+    pub fn flush_region(&mut self, x0: u8, y0: u8, x1: u8, y1: u8) -> Result<(), OledError> {
+        let x1 = x1.min((self.size.width() - 1) as u8);
+        let y1 = y1.min((self.size.height() - 1) as u8);
+        if x0 > x1 || y0 > y1 {
+            return Ok(());
+        }
+
+        let (p0, p1) = (y0 / 8, y1 / 8);
+        self.with_retry(|display| display.flush_window(x0, x1, p0, p1))
+    }
+
+    /// Send columns `x0..=x1` of pages `p0..=p1` once
+    fn flush_window(&mut self, x0: u8, x1: u8, p0: u8, p1: u8) -> Result<(), OledError> {
+        self.set_addr_window(x0, x1, p0, p1)?;
+
+        // The panel fills the window page by page, left to right
+        for page in p0..=p1 {
+            let row = page as usize * self.size.width();
+            let span = &self.buffer[row + x0 as usize..=row + x1 as usize];
+            for chunk in span.chunks(self.chunk_size) {
+                self.interface.data(chunk)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Clear the entire framebuffer
+    pub fn clear(&mut self) -> Result<(), OledError> {
+        let len = self.size.width() * self.size.pages();
+        self.buffer[..len].fill(0);
+        Ok(())
+    }
+
+    /// Move the framebuffer up one page and blank the bottom page
+    fn scroll_up_page(&mut self) {
+        let width = self.size.width();
+        let len = width * self.size.pages();
+        self.buffer.copy_within(width..len, 0);
+        self.buffer[len - width..len].fill(0);
+    }
+
+    /// Turn a single pixel on or off (out-of-range coordinates are ignored)
+    pub fn set_pixel(&mut self, x: u8, y: u8, on: bool) {
+        self.set_pixel_clipped(x as i16, y as i16, on);
+    }
+
+    /// Set a pixel using signed coordinates, clipping anything off-screen
+    fn set_pixel_clipped(&mut self, x: i16, y: i16, on: bool) {
+        let width = self.size.width();
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= self.size.height() {
+            return;
+        }
+
+        let index = (y as usize / 8) * width + x as usize;
+        let mask = 1 << (y % 8);
+        if on {
+            self.buffer[index] |= mask;
+        } else {
+            self.buffer[index] &= !mask;
+        }
+    }
+
+    /// Draw a 1-bit image with its top-left corner at (`x`, `y`)
+    ///
+    /// `bytes` holds rows of `width` pixels, MSB first, each row padded to a
+    /// whole byte; the height is however many full rows `bytes` contains.
+    /// Set bits light pixels and clear bits erase them. Anything past the
+    /// panel edge is clipped.
+    pub fn draw_bitmap(&mut self, x: u8, y: u8, width: u8, bytes: &[u8]) {
+        let stride = (width as usize).div_ceil(8);
+        if stride == 0 {
+            return;
+        }
+
+        let rows = self.size.height().saturating_sub(y as usize);
+        for (row, line) in bytes.chunks_exact(stride).take(rows).enumerate() {
+            let py = y as i16 + row as i16;
+            for column in 0..width as usize {
+                let on = line[column / 8] & (0x80 >> (column % 8)) != 0;
+                self.set_pixel_clipped(x as i16 + column as i16, py, on);
+            }
+        }
+    }
+
+    /// Look up the 5x7 font columns for a character
+    ///
+    /// Anything outside printable ASCII is drawn as a box.
+    fn glyph(c: char) -> [u8; 5] {
+        if !(' '..='~').contains(&c) {
+            return BOX_GLYPH;
+        }
+        FONT5X7[(c as u8).wrapping_sub(0x20) as usize]
+    }
+
+    /// Whether a character cell (5 columns plus spacer) at `x`, page `y` is on screen
+    fn glyph_fits(&self, x: u8, y: u8) -> bool {
+        x as usize + 6 <= self.size.width() && (y as usize) < self.size.pages()
+    }
+
     /// Draw a simple 5x7 character at the specified position
-    pub fn draw_char(&mut self, c: char, x: u8, y: u8) -> Result<(), &'static str> {
-        if x > 122 || y > 7 {
+    pub fn draw_char(&mut self, c: char, x: u8, y: u8) -> Result<(), OledError> {
+        if !self.glyph_fits(x, y) {
             return Ok(()); // Out of bounds
         }
 
-        // Simple 5x7 font for basic ASCII characters
-        let font_data = match c {
-            'H' => [0x7F, 0x08, 0x08, 0x08, 0x7F],
-            'e' => [0x38, 0x54, 0x54, 0x54, 0x18],
-            'l' => [0x00, 0x41, 0x7F, 0x40, 0x00],
-            'o' => [0x38, 0x44, 0x44, 0x44, 0x38],
-            'S' => [0x32, 0x49, 0x49, 0x49, 0x26],
-            'T' => [0x01, 0x01, 0x7F, 0x01, 0x01],
-            'M' => [0x7F, 0x02, 0x0C, 0x02, 0x7F],
-            '3' => [0x42, 0x41, 0x51, 0x69, 0x46],
-            '2' => [0x42, 0x61, 0x51, 0x49, 0x46],
-            'O' => [0x3E, 0x41, 0x41, 0x41, 0x3E],
-            'L' => [0x7F, 0x40, 0x40, 0x40, 0x40],
-            'E' => [0x7F, 0x49, 0x49, 0x49, 0x41],
-            'D' => [0x7F, 0x41, 0x41, 0x22, 0x1C],
-            ' ' => [0x00, 0x00, 0x00, 0x00, 0x00],
-            '!' => [0x00, 0x00, 0x5F, 0x00, 0x00],
-            _ => [0x7F, 0x41, 0x41, 0x41, 0x7F], // Box for unknown chars
-        };
-
-        // Set column address
-        self.write_cmd(0x21)?;
-        self.write_cmd(x)?;
-        self.write_cmd(x + 5)?;
+        // Write character columns into the page, plus a blank spacer column
+        let start = y as usize * self.size.width() + x as usize;
+        self.buffer[start..start + 5].copy_from_slice(&Self::glyph(c));
+        self.buffer[start + 5] = 0x00; // Space between characters
 
-        // Set page address
-        self.write_cmd(0x22)?;
-        self.write_cmd(y)?;
-        self.write_cmd(y)?;
+        Ok(())
+    }
 
-        // Write character data
-        self.write_data(&font_data)?;
-        self.write_data(&[0x00])?; // Space between characters
+    /// Draw a character combining only its lit pixels with `mode`
+    ///
+    /// Unlike `draw_char`, the background and spacer column are left
+    /// alone, so text can be cleared into a filled region or XORed over
+    /// existing content.
+    pub fn draw_char_mode(
+        &mut self,
+        c: char,
+        x: u8,
+        y: u8,
+        mode: DrawMode,
+    ) -> Result<(), OledError> {
+        if !self.glyph_fits(x, y) {
+            return Ok(()); // Out of bounds
+        }
 
+        self.blit_glyph(&Self::glyph(c), x, y, mode);
         Ok(())
     }
 
+    /// Draw a character in inverse: clear pixels where the glyph is set
+    pub fn draw_char_inverse(&mut self, c: char, x: u8, y: u8) -> Result<(), OledError> {
+        self.draw_char_mode(c, x, y, DrawMode::Clear)
+    }
+
+    /// Combine a glyph's columns into a page (caller checks bounds)
+    fn blit_glyph(&mut self, glyph: &[u8; 5], x: u8, y: u8, mode: DrawMode) {
+        let start = y as usize * self.size.width() + x as usize;
+        for (column, &bits) in self.buffer[start..start + 5].iter_mut().zip(glyph) {
+            match mode {
+                DrawMode::Set => *column |= bits,
+                DrawMode::Clear => *column &= !bits,
+                DrawMode::Xor => *column ^= bits,
+            }
+        }
+    }
+
     /// Draw a string at the specified position
-    pub fn draw_text(&mut self, text: &str, x: u8, y: u8) -> Result<(), &'static str> {
+    pub fn draw_text(&mut self, text: &str, x: u8, y: u8) -> Result<(), OledError> {
         let mut pos_x = x;
         for c in text.chars() {
-            if pos_x > 122 {
+            if pos_x as usize + 6 > self.size.width() {
                 break; // No more room
             }
             self.draw_char(c, pos_x, y)?;
@@ -196,27 +915,334 @@ impl OledDisplay {
         Ok(())
     }
 
+    /// Draw an inverted status bar across page 0
+    ///
+    /// The bar is lit with `title` cleared into it on the left (truncated
+    /// to fit) and a check mark or cross on the right depending on `ok`.
+    pub fn draw_status_bar(&mut self, title: &str, ok: bool) -> Result<(), OledError> {
+        let width = self.size.width();
+        self.buffer[..width].fill(0xFF);
+
+        // Indicator glyph is right-aligned
+        let indicator_x = (width - 6) as u8;
+        let mut pos_x = 1;
+        for c in title.chars() {
+            if pos_x + 6 > indicator_x {
+                break; // Keep clear of the indicator
+            }
+            self.draw_char_inverse(c, pos_x, 0)?;
+            pos_x += 6; // 5 pixels + 1 space
+        }
+
+        let indicator = if ok { CHECK_GLYPH } else { CROSS_GLYPH };
+        self.blit_glyph(&indicator, indicator_x, 0, DrawMode::Clear);
+
+        Ok(())
+    }
+
     /// Fill a rectangular area
-    pub fn fill_rect(&mut self, x: u8, y: u8, width: u8, height: u8) -> Result<(), &'static str> {
-        let end_x = (x + width).min(127);
-        let end_y = (y + height / 8).min(7);
+    ///
+    /// `x` and `width` are in pixels; `y` is a page (8-pixel row) and
+    /// `height` is in pixels, rounded up to whole pages. The area is clipped
+    /// at the panel edges and an empty area draws nothing.
+    ///
+    /// `DrawMode::Set` lights the area, `DrawMode::Clear` erases it (for
+    /// redrawing part of the screen without a full `clear()`), and
+    /// `DrawMode::Xor` inverts it.
+    pub fn fill_rect(
+        &mut self,
+        x: u8,
+        y: u8,
+        width: u8,
+        height: u8,
+        mode: DrawMode,
+    ) -> Result<(), OledError> {
+        let stride = self.size.width();
+        let pages = self.size.pages();
+        if x as usize >= stride || y as usize >= pages || width == 0 || height == 0 {
+            return Ok(());
+        }
+        // Inclusive ends, computed in usize so nothing wraps
+        let end_x = (x as usize + width as usize - 1).min(stride - 1);
+        let end_y = (y as usize + (height as usize).div_ceil(8) - 1).min(pages - 1);
 
-        // Set column address
-        self.write_cmd(0x21)?;
-        self.write_cmd(x)?;
-        self.write_cmd(end_x)?;
+        for page in y as usize..=end_y {
+            let row = page * stride;
+            let span = &mut self.buffer[row + x as usize..=row + end_x];
+            match mode {
+                DrawMode::Set => span.fill(0xFF),
+                DrawMode::Clear => span.fill(0x00),
+                DrawMode::Xor => span.iter_mut().for_each(|column| *column ^= 0xFF),
+            }
+        }
 
-        // Set page address
-        self.write_cmd(0x22)?;
-        self.write_cmd(y)?;
-        self.write_cmd(end_y)?;
+        Ok(())
+    }
 
-        // Fill with 0xFF (all pixels on)
-        let fill = [0xFF; 128];
-        for _ in y..=end_y {
-            self.write_data(&fill[0..(end_x - x + 1) as usize])?;
+    /// Draw a line between two points (inclusive) with Bresenham's algorithm
+    pub fn draw_line(&mut self, x0: u8, y0: u8, x1: u8, y1: u8) {
+        let (mut x, mut y) = (x0 as i16, y0 as i16);
+        let (x1, y1) = (x1 as i16, y1 as i16);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.set_pixel_clipped(x, y, true);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
         }
+    }
+
+    /// Draw the outline of a `width` x `height` rectangle
+    ///
+    /// Edges past the screen are clipped; a zero size draws nothing.
+    pub fn draw_rect(&mut self, x: u8, y: u8, width: u8, height: u8) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let (x0, y0) = (x as i16, y as i16);
+        let (x1, y1) = (x0 + width as i16 - 1, y0 + height as i16 - 1);
+        for px in x0..=x1 {
+            self.set_pixel_clipped(px, y0, true);
+            self.set_pixel_clipped(px, y1, true);
+        }
+        for py in y0..=y1 {
+            self.set_pixel_clipped(x0, py, true);
+            self.set_pixel_clipped(x1, py, true);
+        }
+    }
+
+    /// Compute one octant of a circle with the midpoint algorithm
+    ///
+    /// Points are `(x, y)` offsets with `x <= y`, ordered from the top of
+    /// the circle (x = 0) towards the 45 degree diagonal.
+    fn circle_octant(r: u8, points: &mut [(i16, i16); MAX_OCTANT_POINTS]) -> usize {
+        let mut x: i16 = 0;
+        let mut y: i16 = r as i16;
+        let mut d: i16 = 1 - r as i16;
+        let mut count = 0;
+
+        while x <= y {
+            points[count] = (x, y);
+            count += 1;
+
+            x += 1;
+            if d < 0 {
+                d += 2 * x + 1;
+            } else {
+                y -= 1;
+                d += 2 * (x - y) + 1;
+            }
+        }
+
+        count
+    }
+
+    /// Draw a circle outline using the midpoint algorithm
+    pub fn draw_circle(&mut self, cx: u8, cy: u8, r: u8) {
+        let mut points = [(0i16, 0i16); MAX_OCTANT_POINTS];
+        let count = Self::circle_octant(r, &mut points);
+        let (cx, cy) = (cx as i16, cy as i16);
+
+        for &(x, y) in &points[..count] {
+            self.set_pixel_clipped(cx + x, cy - y, true);
+            self.set_pixel_clipped(cx + y, cy - x, true);
+            self.set_pixel_clipped(cx + y, cy + x, true);
+            self.set_pixel_clipped(cx + x, cy + y, true);
+            self.set_pixel_clipped(cx - x, cy + y, true);
+            self.set_pixel_clipped(cx - y, cy + x, true);
+            self.set_pixel_clipped(cx - y, cy - x, true);
+            self.set_pixel_clipped(cx - x, cy - y, true);
+        }
+    }
+
+    /// Draw a circular progress ring
+    ///
+    /// The outer circle is always drawn; an arc starting at 12 o'clock and
+    /// running clockwise is filled in proportion to `percent` (0-100). The
+    /// filled band is roughly a quarter of the radius thick.
+    pub fn draw_progress_ring(&mut self, cx: u8, cy: u8, r: u8, percent: u8) {
+        self.draw_circle(cx, cy, r);
+        if r == 0 {
+            return;
+        }
+
+        let mut points = [(0i16, 0i16); MAX_OCTANT_POINTS];
+        let count = Self::circle_octant(r, &mut points);
+        let total = count * 8;
+        let filled = total * percent.min(100) as usize / 100;
+        let thickness = (r as i16 / 4).max(2).min(r as i16);
+        let (cx, cy, r) = (cx as i16, cy as i16, r as i16);
+
+        // Walk the outline clockwise from 12 o'clock. Even octants run along
+        // the precomputed points, odd octants run back over them.
+        for step in 0..filled {
+            let octant = step / count;
+            let i = if octant % 2 == 0 {
+                step % count
+            } else {
+                count - 1 - step % count
+            };
+            let (x, y) = points[i];
+            let (dx, dy) = match octant {
+                0 => (x, -y),
+                1 => (y, -x),
+                2 => (y, x),
+                3 => (x, y),
+                4 => (-x, y),
+                5 => (-y, x),
+                6 => (-y, -x),
+                _ => (-x, -y),
+            };
+
+            // Radial fill from the outline towards the center. `dx * scale`
+            // reaches r^2, which overflows i16 once r passes 181.
+            for t in 0..thickness {
+                let scale = (r - t) as i32;
+                let (ox, oy) = (dx as i32 * scale / r as i32, dy as i32 * scale / r as i32);
+                self.set_pixel_clipped(cx + ox as i16, cy + oy as i16, true);
+            }
+        }
+    }
+}
+
+impl<DI, EN> OriginDimensions for OledDisplay<DI, EN> {
+    fn size(&self) -> Size {
+        Size::new(self.size.width() as u32, self.size.height() as u32)
+    }
+}
+
+impl<DI: OledInterface, EN: OutputPin> DrawTarget for OledDisplay<DI, EN> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            // Anything beyond i16 is off-screen anyway
+            if let (Ok(x), Ok(y)) = (i16::try_from(point.x), i16::try_from(point.y)) {
+                self.set_pixel_clipped(x, y, color.is_on());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Scrolling text console on top of an `OledDisplay`
+///
+/// Text is laid out in 6-pixel character cells on 8-pixel pages (21 x 8
+/// cells on a 128x64 panel), wraps at the right edge and scrolls the
+/// framebuffer up a page once the bottom is full. `write!` only draws into
+/// the framebuffer; `println` and `flush` push it to the panel.
+pub struct TextConsole<DI = SpiInterface, EN = EnablePin> {
+    display: OledDisplay<DI, EN>,
+    column: u8,
+    /// Page of the cursor; equal to the page count after a line break on
+    /// the bottom page, so the scroll waits for the next character
+    row: u8,
+}
+
+impl<DI: OledInterface, EN: OutputPin> TextConsole<DI, EN> {
+    /// Take over a display, clearing it and starting at the top left
+    pub fn new(mut display: OledDisplay<DI, EN>) -> Self {
+        let _ = display.clear();
+        TextConsole {
+            display,
+            column: 0,
+            row: 0,
+        }
+    }
+
+    /// Characters per line
+    pub fn columns(&self) -> u8 {
+        (self.display.size.width() / 6) as u8
+    }
+
+    /// Lines on screen
+    pub fn rows(&self) -> u8 {
+        self.display.size.pages() as u8
+    }
+
+    /// Write a line of text and push the framebuffer to the panel
+    pub fn println(&mut self, s: &str) -> Result<(), OledError> {
+        self.put_str(s);
+        self.new_line();
+        self.display.flush()
+    }
+
+    /// Push the framebuffer to the panel
+    pub fn flush(&mut self) -> Result<(), OledError> {
+        self.display.flush()
+    }
+
+    /// Blank the screen and return the cursor to the top left
+    pub fn clear(&mut self) -> Result<(), OledError> {
+        self.column = 0;
+        self.row = 0;
+        self.display.clear()
+    }
+
+    /// Access the display, e.g. to adjust contrast
+    pub fn display_mut(&mut self) -> &mut OledDisplay<DI, EN> {
+        &mut self.display
+    }
+
+    /// Give the display back
+    pub fn into_inner(self) -> OledDisplay<DI, EN> {
+        self.display
+    }
+
+    fn put_str(&mut self, s: &str) {
+        for c in s.chars() {
+            match c {
+                '\n' => self.new_line(),
+                '\r' => self.column = 0,
+                c => self.put_char(c),
+            }
+        }
+    }
+
+    fn put_char(&mut self, c: char) {
+        if self.column == self.columns() {
+            self.new_line();
+        }
+        if self.row == self.rows() {
+            self.display.scroll_up_page();
+            self.row -= 1;
+        }
+        let _ = self.display.draw_char(c, self.column * 6, self.row);
+        self.column += 1;
+    }
+
+    fn new_line(&mut self) {
+        self.column = 0;
+        if self.row == self.rows() {
+            self.display.scroll_up_page();
+        } else {
+            self.row += 1;
+        }
+    }
+}
 
+impl<DI: OledInterface, EN: OutputPin> core::fmt::Write for TextConsole<DI, EN> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.put_str(s);
         Ok(())
     }
 }
@@ -0,0 +1,40 @@
+//! Hash-to-field conversions for Groth16 public inputs
+//!
+//! Circuits often expose a hash of some data as their public input. The
+//! device recomputes that input from its own copy of the data before
+//! verifying, so the proof is bound to what the device actually holds.
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use miden_crypto::hash::rpo::Rpo256;
+
+use super::crypto::keccak256;
+
+/// Maps arbitrary bytes to a BN254 scalar field element
+pub trait PublicInputHasher {
+    /// Hash `data` to the public input a circuit expects
+    fn hash_to_fr(&self, data: &[u8]) -> Fr;
+}
+
+/// `uint256(keccak256(data)) % r`, the usual Solidity convention
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeccakReduce;
+
+impl PublicInputHasher for KeccakReduce {
+    fn hash_to_fr(&self, data: &[u8]) -> Fr {
+        Fr::from_be_bytes_mod_order(&keccak256(data))
+    }
+}
+
+/// RPO-256 digest reduced mod r
+///
+/// The four digest elements are read as one little-endian 256-bit integer
+/// (element 0 least significant), matching `RpoDigest::as_bytes`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpoHasher;
+
+impl PublicInputHasher for RpoHasher {
+    fn hash_to_fr(&self, data: &[u8]) -> Fr {
+        Fr::from_le_bytes_mod_order(&Rpo256::hash(data).as_bytes())
+    }
+}
@@ -0,0 +1,138 @@
+//! Minimal RLP (Recursive Length Prefix) encoder and decoder
+//!
+//! Covers the subset needed to build Ethereum transactions: byte strings,
+//! big-endian integers and lists. Items are appended to a caller-owned
+//! `Vec<u8>`; lists are built by encoding their items into a scratch buffer
+//! and wrapping it with `encode_list`.
+//!
+//! Decoding borrows from the input: `decode` splits off one item and
+//! `decode_list` splits a list payload into its items. Only canonical
+//! (minimal) encodings are accepted.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Append the RLP header for a string or list payload of `len` bytes
+fn encode_length(len: usize, offset: u8, out: &mut Vec<u8>) {
+    if len < 56 {
+        out.push(offset + len as u8);
+    } else {
+        let len_bytes = (len as u64).to_be_bytes();
+        let skip = len_bytes.iter().take_while(|&&b| b == 0).count();
+        out.push(offset + 55 + (8 - skip) as u8);
+        out.extend_from_slice(&len_bytes[skip..]);
+    }
+}
+
+/// Encode a byte string
+pub fn encode_bytes(data: &[u8], out: &mut Vec<u8>) {
+    if data.len() == 1 && data[0] < 0x80 {
+        // A single byte below 0x80 is its own encoding
+        out.push(data[0]);
+    } else {
+        encode_length(data.len(), 0x80, out);
+        out.extend_from_slice(data);
+    }
+}
+
+/// Encode an unsigned integer (big-endian, no leading zeros)
+pub fn encode_uint(value: u128, out: &mut Vec<u8>) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+    encode_bytes(&bytes[skip..], out);
+}
+
+/// Encode a 32-byte big-endian integer (leading zeros stripped)
+pub fn encode_uint256(value: &[u8; 32], out: &mut Vec<u8>) {
+    let skip = value.iter().take_while(|&&b| b == 0).count();
+    encode_bytes(&value[skip..], out);
+}
+
+/// Wrap already-encoded items as a list
+pub fn encode_list(payload: &[u8], out: &mut Vec<u8>) {
+    encode_length(payload.len(), 0xc0, out);
+    out.extend_from_slice(payload);
+}
+
+/// Errors returned by RLP decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RlpError {
+    /// The input ends before the item does
+    UnexpectedEnd,
+    /// The item is not minimally encoded
+    NonCanonical,
+}
+
+/// A decoded item, borrowing from the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RlpItem<'a> {
+    /// Byte string contents
+    Bytes(&'a [u8]),
+    /// List payload (the encoded items, without the list header)
+    List(&'a [u8]),
+}
+
+/// Decode the first item of `input`, returning it and the remaining bytes
+pub fn decode(input: &[u8]) -> Result<(RlpItem<'_>, &[u8]), RlpError> {
+    let (&prefix, rest) = input.split_first().ok_or(RlpError::UnexpectedEnd)?;
+
+    let (is_list, len, rest) = match prefix {
+        0x00..=0x7f => return Ok((RlpItem::Bytes(&input[..1]), rest)),
+        0x80..=0xb7 => (false, (prefix - 0x80) as usize, rest),
+        0xb8..=0xbf => {
+            let (len, rest) = decode_long_length(prefix - 0xb7, rest)?;
+            (false, len, rest)
+        }
+        0xc0..=0xf7 => (true, (prefix - 0xc0) as usize, rest),
+        0xf8..=0xff => {
+            let (len, rest) = decode_long_length(prefix - 0xf7, rest)?;
+            (true, len, rest)
+        }
+    };
+
+    if rest.len() < len {
+        return Err(RlpError::UnexpectedEnd);
+    }
+    let (payload, rest) = rest.split_at(len);
+
+    if is_list {
+        Ok((RlpItem::List(payload), rest))
+    } else {
+        // A single byte below 0x80 must be encoded as itself
+        if len == 1 && payload[0] < 0x80 {
+            return Err(RlpError::NonCanonical);
+        }
+        Ok((RlpItem::Bytes(payload), rest))
+    }
+}
+
+/// Decode every item of a list payload
+pub fn decode_list(mut payload: &[u8]) -> Result<Vec<RlpItem<'_>>, RlpError> {
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (item, rest) = decode(payload)?;
+        items.push(item);
+        payload = rest;
+    }
+    Ok(items)
+}
+
+/// Read a big-endian length of `len_bytes` bytes following a long-form prefix
+fn decode_long_length(len_bytes: u8, input: &[u8]) -> Result<(usize, &[u8]), RlpError> {
+    let len_bytes = len_bytes as usize;
+    if input.len() < len_bytes {
+        return Err(RlpError::UnexpectedEnd);
+    }
+    let (len_be, rest) = input.split_at(len_bytes);
+    if len_be[0] == 0 || len_bytes > core::mem::size_of::<usize>() {
+        return Err(RlpError::NonCanonical);
+    }
+
+    let len = len_be
+        .iter()
+        .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+    if len < 56 {
+        return Err(RlpError::NonCanonical);
+    }
+    Ok((len, rest))
+}
@@ -0,0 +1,240 @@
+//! Entropy pool mixing several sources
+//!
+//! Whatever sources a board has (TRNG words, ADC noise LSBs, DWT jitter
+//! between interrupts) are `stir`red into one Keccak-256 state, and output
+//! is squeezed from that state. The pool is at least as unpredictable as
+//! the best source stirred in, even if the others are weak or influenced
+//! by an attacker. It cannot create entropy: a pool fed only from the DWT
+//! cycle counter is still predictable.
+//!
+//! The state is ratcheted after every `fill`, so output already handed out
+//! cannot be recomputed from a later snapshot of the pool.
+//!
+//! On parts without a TRNG, `collect_adc_entropy` harvests thermal noise
+//! from the low bits of a floating ADC input.
+
+use core::num::NonZeroU32;
+
+use k256::elliptic_curve::rand_core::{CryptoRng, Error as RandError06, RngCore as RngCore06};
+use rand_core::RngCore as RngCore09;
+use sha3::{Digest, Keccak256};
+#[cfg(feature = "stm32f4")]
+use stm32f4xx_hal::{
+    adc::{config::SampleTime, Adc, Instance as AdcInstance},
+    hal_02::adc::Channel,
+};
+
+/// Low bits of each noise sample that are kept
+///
+/// On a floating 12-bit input these are dominated by thermal noise; the
+/// higher bits mostly follow slow drift and pickup.
+pub const NOISE_BITS: u32 = 2;
+
+/// Debiased bits stirred into the pool at a time
+const HARVEST_BYTES: usize = 32;
+
+/// Bytes that must be stirred in before the `rand_core` interfaces serve
+///
+/// Counts bytes whatever their quality, so this stops an unseeded pool
+/// from producing one fixed, publicly computable key, not a weak seed.
+pub const MIN_STIRRED_BYTES: u64 = 32;
+
+/// Domain separation tags for the three uses of the hash
+const STIR: u8 = 0x01;
+const SQUEEZE: u8 = 0x02;
+const RATCHET: u8 = 0x03;
+
+/// `try_fill_bytes` error code for a pool below `MIN_STIRRED_BYTES`
+const UNSEEDED: u32 = RandError06::CUSTOM_START;
+
+/// Keccak-256 pool that sources stir into and key generation draws from
+///
+/// Implements both `rand_core` versions, so the same pool can feed k256
+/// (0.6) and miden-crypto's Falcon512 (0.9). Those interfaces panic (or,
+/// for `try_fill_bytes`, return an error) until `MIN_STIRRED_BYTES` have
+/// been stirred in. `CryptoRng` still only holds once those bytes carried
+/// real entropy.
+#[derive(Clone, Default)]
+pub struct EntropyPool {
+    state: [u8; 32],
+    stirred: u64,
+}
+
+impl EntropyPool {
+    /// Empty pool; its output is fixed until something is stirred in
+    pub const fn new() -> Self {
+        EntropyPool {
+            state: [0; 32],
+            stirred: 0,
+        }
+    }
+
+    /// Mix `bytes` into the state
+    ///
+    /// Stirs never cancel out, so feeding a weak or repeated source cannot
+    /// make the pool more predictable.
+    pub fn stir(&mut self, bytes: &[u8]) {
+        self.state = self.hash(STIR, bytes);
+        self.stirred += bytes.len() as u64;
+    }
+
+    /// Mix in one sample, e.g. a TRNG word or a cycle count
+    pub fn stir_u32(&mut self, sample: u32) {
+        self.stir(&sample.to_le_bytes());
+    }
+
+    /// Total bytes stirred in so far, whatever their quality
+    pub fn stirred_bytes(&self) -> u64 {
+        self.stirred
+    }
+
+    /// Whether at least `MIN_STIRRED_BYTES` have been stirred in
+    pub fn is_seeded(&self) -> bool {
+        self.stirred >= MIN_STIRRED_BYTES
+    }
+
+    /// Fill `out` from the pool, then ratchet the state forward
+    ///
+    /// Unlike the `rand_core` interfaces this works on an unseeded pool,
+    /// whose output is fixed.
+    pub fn fill(&mut self, out: &mut [u8]) {
+        for (block, chunk) in out.chunks_mut(32).enumerate() {
+            let bytes = self.hash(SQUEEZE, &(block as u64).to_le_bytes());
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        self.state = self.hash(RATCHET, &[]);
+    }
+
+    /// `keccak256(tag || state || data)`
+    fn hash(&self, tag: u8, data: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update([tag]);
+        hasher.update(self.state);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    /// `fill` for the `rand_core` interfaces, which need a seeded pool
+    fn seeded_fill(&mut self, out: &mut [u8]) {
+        assert!(
+            self.is_seeded(),
+            "EntropyPool used as an RNG before MIN_STIRRED_BYTES were stirred in"
+        );
+        self.fill(out);
+    }
+
+    fn next_u32_internal(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.seeded_fill(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64_internal(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.seeded_fill(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+// Implement RngCore 0.6 for k256 compatibility
+impl RngCore06 for EntropyPool {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u32_internal()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next_u64_internal()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.seeded_fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError06> {
+        if !self.is_seeded() {
+            return Err(NonZeroU32::new(UNSEEDED).unwrap().into());
+        }
+        self.fill(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for EntropyPool {}
+
+// Implement RngCore 0.9 for miden-crypto compatibility
+impl RngCore09 for EntropyPool {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u32_internal()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next_u64_internal()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.seeded_fill(dest);
+    }
+}
+
+/// Von Neumann debias the low bits of `samples` readings into `out`
+///
+/// Readings are taken in pairs and each of the `NOISE_BITS` low bit
+/// positions is compared across the pair: `01` gives 0, `10` gives 1, and
+/// equal bits are dropped. This removes a constant bias from independent
+/// bits, though not correlation between readings. The bits are stirred in
+/// 32-byte batches; returns how many were collected, so the caller can
+/// take more samples if a stuck input yielded too few.
+pub fn collect_entropy(
+    mut sample: impl FnMut() -> u16,
+    samples: usize,
+    out: &mut EntropyPool,
+) -> usize {
+    let mut batch = [0u8; HARVEST_BYTES];
+    let mut bits = 0;
+    let mut total = 0;
+
+    for _ in 0..samples / 2 {
+        let (first, second) = (sample(), sample());
+        for bit in 0..NOISE_BITS {
+            let (a, b) = ((first >> bit) & 1, (second >> bit) & 1);
+            if a == b {
+                continue;
+            }
+            // LSB first within each byte
+            batch[bits / 8] |= (a as u8) << (bits % 8);
+            bits += 1;
+            if bits == HARVEST_BYTES * 8 {
+                out.stir(&batch);
+                batch = [0; HARVEST_BYTES];
+                total += bits;
+                bits = 0;
+            }
+        }
+    }
+
+    if bits > 0 {
+        out.stir(&batch[..bits.div_ceil(8)]);
+        total += bits;
+    }
+    total
+}
+
+/// Harvest noise from a floating ADC input into `out`
+///
+/// Converts `channel` `samples` times at the shortest sample time, which
+/// lets the most noise through, and feeds the readings to
+/// `collect_entropy`. Leave the pin unconnected (or tied to a noisy
+/// source) and in analog mode. Returns the number of debiased bits.
+#[cfg(feature = "stm32f4")]
+pub fn collect_adc_entropy<ADC, PIN>(
+    adc: &mut Adc<ADC>,
+    channel: &PIN,
+    samples: usize,
+    out: &mut EntropyPool,
+) -> usize
+where
+    ADC: AdcInstance,
+    PIN: Channel<ADC, ID = u8>,
+{
+    collect_entropy(|| adc.convert(channel, SampleTime::Cycles_3), samples, out)
+}
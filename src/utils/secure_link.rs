@@ -0,0 +1,230 @@
+//! Encrypted UART link built on ECDH, HKDF and AES-GCM
+//!
+//! Pin connections (USART2):
+//! - TX → PA2
+//! - RX → PA3
+//!
+//! Handshake: each side sends a fresh compressed secp256k1 public key
+//! (33 bytes) and both derive two AES-256 keys from the ECDH shared secret
+//! with HKDF-SHA256. The side with the smaller public key sends with the
+//! first key and receives with the second; the other side swaps them, so the
+//! two directions never reuse a key/nonce pair.
+//!
+//! Frame layout: `counter (u64 BE) || ciphertext || tag`, COBS-encoded and
+//! terminated by a zero byte (see `framing`). The counter is the AES-GCM
+//! nonce and must strictly increase, which rejects replayed frames.
+
+use aes_gcm::{
+    aead::{AeadInPlace, KeyInit},
+    Aes256Gcm, Nonce, Tag,
+};
+use k256::{
+    ecdh::EphemeralSecret,
+    elliptic_curve::{
+        rand_core::{CryptoRng, RngCore},
+        sec1::ToEncodedPoint,
+    },
+    PublicKey,
+};
+use sha2::Sha256;
+use stm32f4xx_hal::{block, pac::USART2, prelude::*, serial::Serial};
+
+use super::framing::{self, max_encoded_len, DELIMITER};
+
+/// Largest plaintext carried in a single frame
+pub const MAX_PAYLOAD: usize = 256;
+
+/// HKDF info string binding derived keys to this protocol
+const HKDF_INFO: &[u8] = b"stm32-tests secure link v1";
+
+const COUNTER_LEN: usize = 8;
+const TAG_LEN: usize = 16;
+
+/// Largest decoded frame: counter, payload and tag
+const MAX_FRAME: usize = COUNTER_LEN + MAX_PAYLOAD + TAG_LEN;
+
+/// Errors reported by the secure link
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkError {
+    /// The UART reported an error
+    Serial,
+    /// The peer sent an invalid or reflected public key
+    Handshake,
+    /// The payload exceeds `MAX_PAYLOAD`
+    PayloadTooLarge,
+    /// The received frame is malformed or has an impossible length
+    BadFrame,
+    /// The caller's buffer is too small for the received payload
+    BufferTooSmall,
+    /// The frame counter did not increase (replayed or reordered frame)
+    Replay,
+    /// Authentication failed (tampered frame or wrong key)
+    Decrypt,
+    /// The send counter is exhausted; a new handshake is required
+    CounterExhausted,
+}
+
+/// Confidential, authenticated channel to a host over USART2
+pub struct SecureChannel {
+    serial: Serial<USART2>,
+    send_cipher: Aes256Gcm,
+    recv_cipher: Aes256Gcm,
+    send_counter: u64,
+    /// Lowest counter value that will be accepted next
+    recv_counter: u64,
+}
+
+impl SecureChannel {
+    /// Perform the ECDH handshake and return a ready-to-use channel
+    pub fn handshake<R: RngCore + CryptoRng>(
+        serial: Serial<USART2>,
+        rng: &mut R,
+    ) -> Result<Self, LinkError> {
+        let mut channel_serial = serial;
+
+        // Exchange ephemeral public keys
+        let secret = EphemeralSecret::random(rng);
+        let local_point = secret.public_key().to_encoded_point(true);
+        let local_bytes = local_point.as_bytes();
+        write_all(&mut channel_serial, local_bytes)?;
+
+        let mut peer_bytes = [0u8; 33];
+        read_exact(&mut channel_serial, &mut peer_bytes)?;
+        if peer_bytes[..] == local_bytes[..] {
+            return Err(LinkError::Handshake); // Reflected key
+        }
+        let peer = PublicKey::from_sec1_bytes(&peer_bytes).map_err(|_| LinkError::Handshake)?;
+
+        // Derive one key per direction from the shared secret
+        let shared = secret.diffie_hellman(&peer);
+        let mut okm = [0u8; 64];
+        shared
+            .extract::<Sha256>(None)
+            .expand(HKDF_INFO, &mut okm)
+            .map_err(|_| LinkError::Handshake)?;
+
+        let (first, second) = okm.split_at(32);
+        let (send_key, recv_key) = if local_bytes[..] < peer_bytes[..] {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let channel = SecureChannel {
+            serial: channel_serial,
+            send_cipher: Aes256Gcm::new(send_key.into()),
+            recv_cipher: Aes256Gcm::new(recv_key.into()),
+            send_counter: 0,
+            recv_counter: 0,
+        };
+        okm.fill(0);
+
+        Ok(channel)
+    }
+
+    /// Encrypt and send one frame
+    pub fn send(&mut self, data: &[u8]) -> Result<(), LinkError> {
+        if data.len() > MAX_PAYLOAD {
+            return Err(LinkError::PayloadTooLarge);
+        }
+        if self.send_counter == u64::MAX {
+            return Err(LinkError::CounterExhausted);
+        }
+
+        let counter = self.send_counter;
+        self.send_counter += 1;
+
+        let mut frame = [0u8; MAX_FRAME];
+        let frame_len = COUNTER_LEN + data.len() + TAG_LEN;
+        frame[..COUNTER_LEN].copy_from_slice(&counter.to_be_bytes());
+        let payload = &mut frame[COUNTER_LEN..COUNTER_LEN + data.len()];
+        payload.copy_from_slice(data);
+        let tag = self
+            .send_cipher
+            .encrypt_in_place_detached(&nonce_for(counter), b"", payload)
+            .map_err(|_| LinkError::Decrypt)?;
+        frame[COUNTER_LEN + data.len()..frame_len].copy_from_slice(&tag);
+
+        let mut encoded = [0u8; max_encoded_len(MAX_FRAME)];
+        let encoded_len = framing::encode_frame(&frame[..frame_len], &mut encoded)
+            .map_err(|_| LinkError::BadFrame)?;
+        write_all(&mut self.serial, &encoded[..encoded_len])
+    }
+
+    /// Receive and decrypt one frame, returning the payload length
+    pub fn recv(&mut self, out: &mut [u8]) -> Result<usize, LinkError> {
+        // Collect bytes up to the delimiter
+        let mut encoded = [0u8; max_encoded_len(MAX_FRAME)];
+        let mut encoded_len = 0;
+        let mut overflow = false;
+        loop {
+            let byte = block!(self.serial.read()).map_err(|_| LinkError::Serial)?;
+            if byte == DELIMITER {
+                break;
+            }
+            if encoded_len < encoded.len() {
+                encoded[encoded_len] = byte;
+                encoded_len += 1;
+            } else {
+                overflow = true; // Keep draining until the delimiter
+            }
+        }
+        if overflow {
+            return Err(LinkError::BadFrame);
+        }
+
+        let mut frame = [0u8; MAX_FRAME];
+        let frame_len = framing::decode_frame(&encoded[..encoded_len], &mut frame)
+            .map_err(|_| LinkError::BadFrame)?;
+        if frame_len < COUNTER_LEN + TAG_LEN {
+            return Err(LinkError::BadFrame);
+        }
+        let payload_len = frame_len - COUNTER_LEN - TAG_LEN;
+
+        let mut counter_bytes = [0u8; COUNTER_LEN];
+        counter_bytes.copy_from_slice(&frame[..COUNTER_LEN]);
+        let counter = u64::from_be_bytes(counter_bytes);
+        if counter < self.recv_counter {
+            return Err(LinkError::Replay);
+        }
+        if payload_len > out.len() {
+            return Err(LinkError::BufferTooSmall);
+        }
+
+        let mut tag = Tag::default();
+        tag.copy_from_slice(&frame[COUNTER_LEN + payload_len..frame_len]);
+        let payload = &mut frame[COUNTER_LEN..COUNTER_LEN + payload_len];
+        self.recv_cipher
+            .decrypt_in_place_detached(&nonce_for(counter), b"", payload, &tag)
+            .map_err(|_| LinkError::Decrypt)?;
+
+        // Only an authenticated frame may advance the replay window
+        self.recv_counter = counter.saturating_add(1);
+        out[..payload_len].copy_from_slice(payload);
+
+        Ok(payload_len)
+    }
+}
+
+/// Build the 96-bit AES-GCM nonce from a frame counter
+fn nonce_for(counter: u64) -> Nonce<aes_gcm::aes::cipher::consts::U12> {
+    let mut nonce = Nonce::default();
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+/// Blocking write of a byte slice to the UART
+fn write_all(serial: &mut Serial<USART2>, data: &[u8]) -> Result<(), LinkError> {
+    for &byte in data {
+        block!(serial.write(byte)).map_err(|_| LinkError::Serial)?;
+    }
+    Ok(())
+}
+
+/// Blocking read that fills the whole buffer from the UART
+fn read_exact(serial: &mut Serial<USART2>, buffer: &mut [u8]) -> Result<(), LinkError> {
+    for byte in buffer.iter_mut() {
+        *byte = block!(serial.read()).map_err(|_| LinkError::Serial)?;
+    }
+    Ok(())
+}
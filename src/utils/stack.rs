@@ -0,0 +1,68 @@
+//! Stack usage measurement by painting
+//!
+//! Call `paint()` early in `main` to fill the unused stack region with a
+//! known pattern, run the operation of interest, then call
+//! `high_water_mark()` to find how deep the stack reached. The stack grows
+//! down from `_stack_start` (top of RAM) towards `_stack_end` (end of
+//! static data), both provided by cortex-m-rt's linker script.
+//!
+//! Note that a heap placed in a `static` lives below `_stack_end`, so it is
+//! not part of the measured region.
+
+/// Pattern written into unused stack words
+const PAINT: u32 = 0xC0DE_57AC;
+
+/// Bytes below the current stack pointer left untouched while painting
+const SAFETY_MARGIN: usize = 64;
+
+extern "C" {
+    static _stack_start: u32;
+    static _stack_end: u32;
+}
+
+/// Highest stack address (the initial stack pointer)
+fn stack_top() -> usize {
+    core::ptr::addr_of!(_stack_start) as usize
+}
+
+/// Lowest address the stack may grow into
+fn stack_bottom() -> usize {
+    core::ptr::addr_of!(_stack_end) as usize
+}
+
+/// Total size of the stack region in bytes
+pub fn stack_size() -> usize {
+    stack_top() - stack_bottom()
+}
+
+/// Fill the unused part of the stack with the paint pattern
+#[inline(never)]
+pub fn paint() {
+    let sp = cortex_m::register::msp::read() as usize;
+    let end = sp.saturating_sub(SAFETY_MARGIN) & !0x3;
+    let mut addr = stack_bottom();
+
+    while addr < end {
+        unsafe { core::ptr::write_volatile(addr as *mut u32, PAINT) };
+        addr += 4;
+    }
+}
+
+/// Peak stack usage in bytes since the last `paint()`
+///
+/// Scans up from the bottom of the stack for the first word that no longer
+/// holds the paint pattern. If the whole region was overwritten the stack
+/// has likely overflowed, and the full stack size is returned.
+pub fn high_water_mark() -> usize {
+    let top = stack_top();
+    let mut addr = stack_bottom();
+
+    while addr < top {
+        if unsafe { core::ptr::read_volatile(addr as *const u32) } != PAINT {
+            break;
+        }
+        addr += 4;
+    }
+
+    top - addr
+}
@@ -0,0 +1,52 @@
+//! Wall-clock timing from the DWT cycle counter
+//!
+//! The cycle counter must be enabled first (`DCB::enable_trace` and
+//! `DWT::enable_cycle_counter`). It wraps after 2^32 cycles, about 51 s at
+//! 84 MHz, so only intervals shorter than that are measured correctly.
+
+use cortex_m::peripheral::DWT;
+use stm32f4xx_hal::rcc::Clocks;
+
+/// Converts DWT cycle counts to time at the configured core clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleTimer {
+    sysclk_hz: u32,
+}
+
+impl CycleTimer {
+    /// Timer for a core running at `sysclk_hz`
+    pub const fn new(sysclk_hz: u32) -> Self {
+        assert!(sysclk_hz > 0, "core clock must be non-zero");
+        CycleTimer { sysclk_hz }
+    }
+
+    /// Timer for the core clock chosen when the RCC was frozen
+    pub fn from_clocks(clocks: &Clocks) -> Self {
+        Self::new(clocks.sysclk().raw())
+    }
+
+    /// Core clock frequency in Hz
+    pub fn sysclk_hz(&self) -> u32 {
+        self.sysclk_hz
+    }
+
+    /// Current cycle count, to pass to `elapsed_cycles` later
+    pub fn start(&self) -> u32 {
+        DWT::cycle_count()
+    }
+
+    /// Cycles since `start`, correct across one counter wrap
+    pub fn elapsed_cycles(&self, start: u32) -> u32 {
+        DWT::cycle_count().wrapping_sub(start)
+    }
+
+    /// Convert a cycle count to microseconds, rounding down
+    pub fn cycles_to_us(&self, cycles: u32) -> u32 {
+        (cycles as u64 * 1_000_000 / self.sysclk_hz as u64) as u32
+    }
+
+    /// Convert a cycle count to milliseconds, rounding down
+    pub fn cycles_to_ms(&self, cycles: u32) -> u32 {
+        (cycles as u64 * 1_000 / self.sysclk_hz as u64) as u32
+    }
+}
@@ -0,0 +1,98 @@
+//! Replayable event transcript kept in RAM
+//!
+//! `transcript!` records a short formatted message, stamped with the DWT
+//! cycle counter, into a fixed-size ring buffer. Only the most recent
+//! `CAPACITY` events are kept. `dump()` prints them through `log!`, oldest
+//! first, so the sequence leading up to a failure can be inspected afterwards
+//! without a probe having been attached while it happened. It can also be
+//! called from a panic handler before halting.
+
+use core::cell::RefCell;
+use core::fmt::{self, Write};
+
+use cortex_m::interrupt::{self, Mutex};
+use heapless::{HistoryBuffer, String};
+
+use crate::log;
+
+/// Number of events kept
+pub const CAPACITY: usize = 32;
+
+/// Longest message stored per event; longer messages are truncated
+pub const MESSAGE_LEN: usize = 64;
+
+/// A recorded event
+#[derive(Clone)]
+pub struct Event {
+    /// DWT cycle count when the event was recorded
+    pub cycles: u32,
+    pub message: String<MESSAGE_LEN>,
+}
+
+static TRANSCRIPT: Mutex<RefCell<HistoryBuffer<Event, CAPACITY>>> =
+    Mutex::new(RefCell::new(HistoryBuffer::new()));
+
+/// Record an event (usually through the `transcript!` macro)
+pub fn record(args: fmt::Arguments) {
+    let mut message = Truncating(String::new());
+    let _ = message.write_fmt(args);
+    let event = Event {
+        cycles: cortex_m::peripheral::DWT::cycle_count(),
+        message: message.0,
+    };
+
+    interrupt::free(|cs| TRANSCRIPT.borrow(cs).borrow_mut().write(event));
+}
+
+/// Print every recorded event through `log!`, oldest first
+///
+/// Each event is copied out under a short critical section and printed
+/// with interrupts enabled, since a UART backend blocks for the whole
+/// line. Events recorded while the dump runs push older ones out, so some
+/// lines may be skipped or repeated.
+pub fn dump() {
+    let len = interrupt::free(|cs| TRANSCRIPT.borrow(cs).borrow().len());
+    log!("--- Transcript ({} events) ---", len);
+    for i in 0..len {
+        let event = interrupt::free(|cs| {
+            TRANSCRIPT
+                .borrow(cs)
+                .borrow()
+                .oldest_ordered()
+                .nth(i)
+                .cloned()
+        });
+        match event {
+            Some(event) => log!("[{:>10}] {}", event.cycles, event.message),
+            None => break,
+        }
+    }
+    log!("--- End of transcript ---");
+}
+
+/// Forget all recorded events
+pub fn clear() {
+    interrupt::free(|cs| TRANSCRIPT.borrow(cs).borrow_mut().clear());
+}
+
+/// Writer that silently drops whatever does not fit
+struct Truncating(String<MESSAGE_LEN>);
+
+impl Write for Truncating {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.0.push(c).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Record a formatted event in the transcript
+#[macro_export]
+macro_rules! transcript {
+    ($($arg:tt)*) => {
+        $crate::utils::transcript::record(format_args!($($arg)*))
+    };
+}
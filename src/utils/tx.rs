@@ -0,0 +1,86 @@
+//! Ethereum transaction building and signing
+//!
+//! Typed EIP-1559 transactions carry the chain id in their payload and
+//! encode the signature's recovery bit as a bare y-parity (0 or 1). Legacy
+//! transactions instead fold the chain id into `v` as described by EIP-155;
+//! `eip155_v` computes that value.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use k256::ecdsa::{RecoveryId, Signature};
+
+use super::crypto::{keccak256, EthereumKeyPair};
+use super::rlp;
+
+/// EIP-2718 type byte for EIP-1559 transactions
+const EIP1559_TX_TYPE: u8 = 0x02;
+
+/// EIP-1559 (type 2) transaction with an empty access list
+pub struct Eip1559Transaction<'a> {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    pub to: [u8; 20],
+    pub value: u128,
+    pub data: &'a [u8],
+}
+
+impl Eip1559Transaction<'_> {
+    /// RLP-encode the unsigned transaction fields (without the list header)
+    fn encode_fields(&self, out: &mut Vec<u8>) {
+        rlp::encode_uint(self.chain_id as u128, out);
+        rlp::encode_uint(self.nonce as u128, out);
+        rlp::encode_uint(self.max_priority_fee_per_gas, out);
+        rlp::encode_uint(self.max_fee_per_gas, out);
+        rlp::encode_uint(self.gas_limit as u128, out);
+        rlp::encode_bytes(&self.to, out);
+        rlp::encode_uint(self.value, out);
+        rlp::encode_bytes(self.data, out);
+        rlp::encode_list(&[], out); // Empty access list
+    }
+
+    /// Compute the hash that is signed: `keccak256(0x02 || rlp(fields))`
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut fields = Vec::new();
+        self.encode_fields(&mut fields);
+
+        let mut payload = Vec::with_capacity(fields.len() + 4);
+        payload.push(EIP1559_TX_TYPE);
+        rlp::encode_list(&fields, &mut payload);
+        keccak256(&payload)
+    }
+
+    /// Encode the signed transaction, ready for `eth_sendRawTransaction`
+    pub fn encode_signed(&self, signature: &Signature, recovery_id: RecoveryId) -> Vec<u8> {
+        let sig_bytes = signature.to_bytes();
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&sig_bytes[0..32]);
+        s.copy_from_slice(&sig_bytes[32..64]);
+
+        let mut fields = Vec::new();
+        self.encode_fields(&mut fields);
+        rlp::encode_uint(recovery_id.is_y_odd() as u128, &mut fields);
+        rlp::encode_uint256(&r, &mut fields);
+        rlp::encode_uint256(&s, &mut fields);
+
+        let mut raw = Vec::with_capacity(fields.len() + 4);
+        raw.push(EIP1559_TX_TYPE);
+        rlp::encode_list(&fields, &mut raw);
+        raw
+    }
+
+    /// Sign the transaction and return the raw signed encoding
+    pub fn sign(&self, keypair: &EthereumKeyPair) -> Result<Vec<u8>, ecdsa::Error> {
+        let (signature, recovery_id) = keypair.sign_hash_recoverable(&self.signing_hash())?;
+        Ok(self.encode_signed(&signature, recovery_id))
+    }
+}
+
+/// Compute the EIP-155 `v` value for a legacy transaction
+pub fn eip155_v(chain_id: u64, recovery_id: RecoveryId) -> u64 {
+    chain_id * 2 + 35 + recovery_id.is_y_odd() as u64
+}
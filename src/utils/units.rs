@@ -0,0 +1,113 @@
+//! Human-readable formatting of token amounts
+//!
+//! Amounts are 256-bit big-endian integers in the token's smallest unit
+//! (e.g. wei). Formatting uses only integer arithmetic and the caller's
+//! buffer, so it works without floating point or a heap.
+
+/// Decimals of ether (1 ETH = 10^18 wei)
+pub const ETHER_DECIMALS: u8 = 18;
+
+/// Significant fractional digits shown
+pub const FRACTION_DIGITS: usize = 4;
+
+/// Digits in the largest 256-bit value
+const MAX_DIGITS: usize = 78;
+
+/// Buffer size that fits any amount with up to `MAX_DIGITS` decimals
+pub const MAX_FORMATTED_LEN: usize = MAX_DIGITS + 2 + FRACTION_DIGITS;
+
+/// Write the decimal digits of a big-endian integer, most significant first
+///
+/// Returns the number of digits; zero is written as a single `0`.
+fn to_decimal(value: &[u8; 32], digits: &mut [u8; MAX_DIGITS]) -> usize {
+    let mut remaining = *value;
+    let mut reversed = [0u8; MAX_DIGITS];
+    let mut count = 0;
+
+    loop {
+        // Long division by 10, one byte at a time
+        let mut remainder = 0u16;
+        for byte in remaining.iter_mut() {
+            let current = (remainder << 8) | *byte as u16;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        reversed[count] = b'0' + remainder as u8;
+        count += 1;
+
+        if remaining.iter().all(|&b| b == 0) {
+            break;
+        }
+    }
+
+    for (i, digit) in reversed[..count].iter().rev().enumerate() {
+        digits[i] = *digit;
+    }
+    count
+}
+
+/// Format an amount with `decimals` decimal places, e.g. wei as ether
+///
+/// Whole amounts keep up to `FRACTION_DIGITS` fractional digits; amounts
+/// below one keep that many significant digits after any leading zeros.
+/// Extra digits are truncated, not rounded, and trailing zeros are dropped
+/// down to a single `0` (`1.0`, `0.0025`, `0.000000000000000001`). At most
+/// 78 fractional digits are written, so with more decimals than that a
+/// tiny amount truncates to `0.0`.
+///
+/// Panics if `buf` is too small; `MAX_FORMATTED_LEN` bytes suffice for any
+/// amount and any `decimals`.
+pub fn format_wei_as_ether<'a>(wei: &[u8; 32], decimals: u8, buf: &'a mut [u8]) -> &'a str {
+    let mut digits = [0u8; MAX_DIGITS];
+    let digit_count = to_decimal(wei, &mut digits);
+    let digits = &digits[..digit_count];
+    let decimals = decimals as usize;
+
+    // Fractional digit `i` (0 = first after the point)
+    let fraction_digit = |i: usize| -> u8 {
+        let position = digit_count as isize - decimals as isize + i as isize;
+        if position < 0 {
+            b'0'
+        } else {
+            digits[position as usize]
+        }
+    };
+
+    let mut len = 0;
+    if digit_count > decimals {
+        let integer = &digits[..digit_count - decimals];
+        buf[..integer.len()].copy_from_slice(integer);
+        len = integer.len();
+    } else {
+        buf[0] = b'0';
+        len += 1;
+    }
+
+    if decimals == 0 {
+        return core::str::from_utf8(&buf[..len]).unwrap();
+    }
+
+    // Pick how many fractional digits to show
+    let leading_zeros = if digit_count > decimals {
+        0
+    } else {
+        (0..decimals)
+            .position(|i| fraction_digit(i) != b'0')
+            .unwrap_or(decimals)
+    };
+    let mut shown = (leading_zeros + FRACTION_DIGITS)
+        .min(decimals)
+        .min(MAX_DIGITS);
+    while shown > 1 && fraction_digit(shown - 1) == b'0' {
+        shown -= 1;
+    }
+
+    buf[len] = b'.';
+    len += 1;
+    for i in 0..shown {
+        buf[len] = fraction_digit(i);
+        len += 1;
+    }
+
+    core::str::from_utf8(&buf[..len]).unwrap()
+}
@@ -0,0 +1,70 @@
+//! Pluggable signature validation
+//!
+//! Authorization code asks a `SignatureValidator` whether a signature over a
+//! hash is acceptable, instead of hardwiring `ecrecover`. `EoaValidator`
+//! covers plain accounts (externally owned accounts).
+//!
+//! Smart-contract wallets (ERC-1271) validate signatures in contract code:
+//! `isValidSignature(hash, signature)` must return the magic value
+//! `0x1626ba7e`. The device cannot run that call itself, but a validator can
+//! forward it to a trusted host that does, e.g. over the secure link:
+//!
+//! ```ignore
+//! struct Erc1271Validator<'a> {
+//!     wallet: [u8; 20],
+//!     host: &'a RefCell<SecureChannel>,
+//! }
+//!
+//! impl SignatureValidator for Erc1271Validator<'_> {
+//!     fn is_valid(&self, hash: &[u8; 32], sig: &[u8]) -> bool {
+//!         // Send wallet || hash || sig, the host makes the eth_call and
+//!         // replies with the 4-byte return value
+//!         ask_host(self.host, &self.wallet, hash, sig) == ERC1271_MAGIC_VALUE
+//!     }
+//! }
+//! ```
+
+use super::crypto::{
+    addresses_equal, ecrecover, recover_address_from_hash, signature_from_eip2098,
+};
+
+/// Value an ERC-1271 wallet returns from `isValidSignature` on success
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Decides whether a signature over a hash is acceptable
+pub trait SignatureValidator {
+    /// Return true if `sig` is a valid signature over `hash`
+    fn is_valid(&self, hash: &[u8; 32], sig: &[u8]) -> bool;
+}
+
+/// Validator for an externally owned account: `ecrecover` plus an address check
+///
+/// Accepts 65-byte `r || s || v` signatures (`v` of 0/1 or 27/28) and 64-byte
+/// EIP-2098 compact signatures.
+pub struct EoaValidator {
+    pub address: [u8; 20],
+}
+
+impl EoaValidator {
+    /// Accept signatures made by `address`
+    pub fn new(address: [u8; 20]) -> Self {
+        EoaValidator { address }
+    }
+}
+
+impl SignatureValidator for EoaValidator {
+    fn is_valid(&self, hash: &[u8; 32], sig: &[u8]) -> bool {
+        let signer = if let Ok(sig_bytes) = <&[u8; 65]>::try_from(sig) {
+            ecrecover(hash, sig_bytes).ok()
+        } else if let Ok(compact) = <&[u8; 64]>::try_from(sig) {
+            signature_from_eip2098(compact)
+                .and_then(|(signature, recovery_id)| {
+                    recover_address_from_hash(hash, &signature, recovery_id)
+                })
+                .ok()
+        } else {
+            None
+        };
+        signer.is_some_and(|signer| addresses_equal(&signer, &self.address))
+    }
+}
@@ -1,5 +1,5 @@
 use p256::ecdsa::{signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey};
-use rand_core::OsRng;
+use rand_core06::OsRng;
 use sha2::{Digest, Sha256};
 
 fn main() {
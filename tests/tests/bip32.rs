@@ -0,0 +1,113 @@
+//! BIP-32 derivation against the reference test vectors
+
+#[allow(dead_code)]
+#[path = "../../src/utils/bip32.rs"]
+mod bip32;
+#[allow(dead_code)]
+#[path = "../../src/utils/bip39.rs"]
+mod bip39;
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+
+use bip32::{Bip32Error, ExtendedKey, HARDENED};
+
+/// Test vector 1 from BIP-32, private keys and chain codes along `m/0'/1/2'/2/1000000000`
+#[test]
+fn test_vector_1_chain() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let expected = [
+        (
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+        ),
+        (
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+        ),
+        (
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+        ),
+        (
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+        ),
+        (
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+            "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+        ),
+        (
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+        ),
+    ];
+
+    let mut key = ExtendedKey::from_seed(&seed).unwrap();
+    let indices = [HARDENED, 1, HARDENED + 2, 2, 1_000_000_000];
+    for (depth, (private, chain_code)) in expected.iter().enumerate() {
+        if depth > 0 {
+            key = key.derive_child(indices[depth - 1]).unwrap();
+        }
+        assert_eq!(key.depth() as usize, depth);
+        assert_eq!(hex::encode(key.to_private_key_bytes()), *private);
+        assert_eq!(hex::encode(key.chain_code()), *chain_code);
+    }
+
+    let master = ExtendedKey::from_seed(&seed).unwrap();
+    let by_path = master.derive_path("m/0'/1/2h/2/1000000000").unwrap();
+    assert_eq!(by_path.to_private_key_bytes(), key.to_private_key_bytes());
+}
+
+/// The widely used "abandon ... about" mnemonic's first Ethereum account
+#[test]
+fn ethereum_account_from_mnemonic() {
+    let mut seed = [0u8; 64];
+    bip39::mnemonic_to_seed(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "",
+        &mut seed,
+    );
+
+    let account = ExtendedKey::from_seed(&seed)
+        .unwrap()
+        .derive_path("m/44'/60'/0'/0/0")
+        .unwrap();
+    let keypair =
+        crypto::EthereumKeyPair::from_private_key_bytes(&account.to_private_key_bytes()).unwrap();
+    assert_eq!(
+        hex::encode(keypair.address),
+        "9858effd232b4033e47d90003d41ec34ecaeda94"
+    );
+}
+
+#[test]
+fn malformed_paths_and_seeds_are_rejected() {
+    let master = ExtendedKey::from_seed(&[0x42; 32]).unwrap();
+    for path in [
+        "",
+        "44'/60'",
+        "m/",
+        "m//0",
+        "m/x",
+        "m/+1",
+        "m/2147483648",
+        "m/1'h",
+    ] {
+        assert_eq!(
+            master.derive_path(path).err(),
+            Some(Bip32Error::InvalidPath),
+            "{path}"
+        );
+    }
+    assert_eq!(master.derive_path("m").unwrap().depth(), 0);
+
+    assert_eq!(
+        ExtendedKey::from_seed(&[0; 15]).err(),
+        Some(Bip32Error::InvalidSeed)
+    );
+    assert_eq!(
+        ExtendedKey::from_seed(&[0; 65]).err(),
+        Some(Bip32Error::InvalidSeed)
+    );
+}
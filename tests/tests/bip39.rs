@@ -0,0 +1,97 @@
+//! BIP-39 checks against the reference test vectors (passphrase "TREZOR")
+
+#[allow(dead_code)]
+#[path = "../../src/utils/bip39.rs"]
+mod bip39;
+
+use bip39::{mnemonic_to_seed, validate_mnemonic, Bip39Error, ENGLISH};
+
+const VECTORS: [(&str, &str); 4] = [
+    (
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+    ),
+    (
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+    ),
+    (
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+        "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8",
+    ),
+    (
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+        "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+    ),
+];
+
+#[test]
+fn reference_vectors_validate_and_derive_seed() {
+    for (phrase, seed) in VECTORS {
+        assert_eq!(validate_mnemonic(phrase), Ok(()), "{phrase}");
+
+        let mut out = [0u8; 64];
+        mnemonic_to_seed(phrase, "TREZOR", &mut out);
+        assert_eq!(hex::encode(out), seed, "{phrase}");
+    }
+}
+
+#[test]
+fn twenty_four_words_validate() {
+    let phrase = [["abandon"; 23].join(" ").as_str(), "art"].join(" ");
+    assert_eq!(validate_mnemonic(&phrase), Ok(()));
+}
+
+/// Extra whitespace must not change the seed, whichever HMAC key path the
+/// phrase takes (the 24-word phrase is longer than a SHA-512 block)
+#[test]
+fn seed_ignores_whitespace_between_words() {
+    let long = [["abandon"; 23].join(" ").as_str(), "art"].join(" ");
+    let cases = [
+        (VECTORS[0].0, VECTORS[0].1),
+        (
+            long.as_str(),
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+        ),
+    ];
+    for (phrase, seed) in cases {
+        let variants = [
+            phrase.to_string(),
+            phrase.replace(' ', "  "),
+            phrase.replace(' ', "\t"),
+            format!(" {phrase}\n"),
+        ];
+        for variant in &variants {
+            assert_eq!(validate_mnemonic(variant), Ok(()), "{variant:?}");
+
+            let mut out = [0u8; 64];
+            mnemonic_to_seed(variant, "TREZOR", &mut out);
+            assert_eq!(hex::encode(out), seed, "{variant:?}");
+        }
+    }
+}
+
+#[test]
+fn bad_phrases_are_rejected() {
+    assert_eq!(
+        validate_mnemonic(&["abandon"; 12].join(" ")),
+        Err(Bip39Error::InvalidChecksum)
+    );
+    assert_eq!(
+        validate_mnemonic(&["abandon"; 11].join(" ")),
+        Err(Bip39Error::InvalidWordCount)
+    );
+    assert_eq!(
+        validate_mnemonic(&["abandon"; 27].join(" ")),
+        Err(Bip39Error::InvalidWordCount)
+    );
+    assert_eq!(
+        validate_mnemonic("abandon abandon satoshis"),
+        Err(Bip39Error::UnknownWord(2))
+    );
+}
+
+#[test]
+fn wordlist_is_sorted_and_unique() {
+    assert!(ENGLISH.windows(2).all(|pair| pair[0] < pair[1]));
+}
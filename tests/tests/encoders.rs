@@ -0,0 +1,736 @@
+//! Host tests for the byte-level encoders shared with the firmware
+//!
+//! The firmware modules are `no_std` and only depend on portable crates, so
+//! they are compiled here directly from `src/utils` for the host.
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+
+use crypto::{bytes_to_hex_string, hex_to_bytes, Eip712Struct, MultisigTransaction};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn hex_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+        let mut hex_buffer = vec![0u8; bytes.len() * 2];
+        let hex = bytes_to_hex_string(&bytes, &mut hex_buffer);
+
+        let mut decoded = vec![0u8; bytes.len()];
+        let len = hex_to_bytes(hex, &mut decoded).unwrap();
+        prop_assert_eq!(&decoded[..len], &bytes[..]);
+    }
+
+    #[test]
+    fn hex_round_trip_with_prefix_and_whitespace(
+        bytes in proptest::collection::vec(any::<u8>(), 0..64),
+        leading in "[ \t\r\n]{0,4}",
+        trailing in "[ \t\r\n]{0,4}",
+    ) {
+        let mut hex_buffer = vec![0u8; bytes.len() * 2];
+        let hex = bytes_to_hex_string(&bytes, &mut hex_buffer).to_lowercase();
+        let input = format!("{leading}0x{hex}{trailing}");
+
+        let mut decoded = vec![0u8; bytes.len()];
+        let len = hex_to_bytes(&input, &mut decoded).unwrap();
+        prop_assert_eq!(&decoded[..len], &bytes[..]);
+    }
+
+    #[test]
+    fn multisig_hash_is_deterministic(
+        to in any::<[u8; 20]>(),
+        value in any::<u64>(),
+        data in proptest::collection::vec(any::<u8>(), 0..128),
+        nonce in any::<u64>(),
+    ) {
+        let first = MultisigTransaction::new(to, value, &data, nonce);
+        let second = MultisigTransaction::new(to, value, &data, nonce);
+        prop_assert_eq!(first.hash_struct(), second.hash_struct());
+    }
+
+    #[test]
+    fn multisig_hash_changes_with_every_field(
+        to in any::<[u8; 20]>(),
+        other_to in any::<[u8; 20]>(),
+        value in any::<u64>(),
+        other_value in any::<u64>(),
+        data in proptest::collection::vec(any::<u8>(), 0..128),
+        other_data in proptest::collection::vec(any::<u8>(), 0..128),
+        nonce in any::<u64>(),
+        other_nonce in any::<u64>(),
+    ) {
+        prop_assume!(to != other_to);
+        prop_assume!(value != other_value);
+        prop_assume!(data != other_data);
+        prop_assume!(nonce != other_nonce);
+
+        let base = MultisigTransaction::new(to, value, &data, nonce).hash_struct();
+
+        let changed = [
+            MultisigTransaction::new(other_to, value, &data, nonce),
+            MultisigTransaction::new(to, other_value, &data, nonce),
+            MultisigTransaction::new(to, value, &other_data, nonce),
+            MultisigTransaction::new(to, value, &data, other_nonce),
+        ];
+        for tx in &changed {
+            prop_assert_ne!(tx.hash_struct(), base);
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn streaming_keccak_matches_one_shot(
+        data in proptest::collection::vec(any::<u8>(), 0..512),
+        split in any::<prop::sample::Index>(),
+    ) {
+        let (head, tail) = data.split_at(split.index(data.len() + 1));
+        let mut hasher = crypto::Keccak256Hasher::new();
+        hasher.update(head);
+        hasher.update(tail);
+        prop_assert_eq!(hasher.finalize(), crypto::keccak256(&data));
+    }
+}
+
+#[test]
+fn keccak_of_empty_input() {
+    assert_eq!(
+        hex::encode(crypto::Keccak256Hasher::new().finalize()),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
+
+#[test]
+fn hex_to_array_requires_exact_length() {
+    let address: [u8; 20] =
+        crypto::hex_to_array("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
+    assert_eq!(
+        hex::encode(address),
+        "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+    );
+
+    let short = crypto::hex_to_array::<20>("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea");
+    let long = crypto::hex_to_array::<20>("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed00");
+    assert_eq!(short, Err(crypto::HexError::WrongLength));
+    assert_eq!(long, Err(crypto::HexError::WrongLength));
+    assert_eq!(
+        crypto::hex_to_array::<1>("0xzz"),
+        Err(crypto::HexError::InvalidChar(2))
+    );
+    assert_eq!(
+        crypto::hex_to_array::<1>("abc"),
+        Err(crypto::HexError::OddLength)
+    );
+}
+
+#[test]
+fn hex_decode_names_match_hex_to_bytes() {
+    let mut out = [0u8; 2];
+    assert_eq!(crypto::hex_decode("0xabCD", &mut out), Ok(2));
+    assert_eq!(out, [0xAB, 0xCD]);
+    assert_eq!(crypto::hex_decode_fixed::<2>("abcd"), Ok([0xAB, 0xCD]));
+    assert_eq!(
+        crypto::hex_decode_fixed::<2>("abcdef"),
+        Err(crypto::HexError::WrongLength)
+    );
+}
+
+#[test]
+fn hex_to_bytes_cases() {
+    use crypto::HexError::{InvalidChar, OddLength, OutputTooSmall};
+
+    let ok: &[(&str, &[u8])] = &[
+        ("", &[]),
+        ("0x", &[]),
+        ("00ff", &[0x00, 0xFF]),
+        ("0xdeadBEEF", &[0xDE, 0xAD, 0xBE, 0xEF]),
+        ("0XaBcD", &[0xAB, 0xCD]),
+        (" \t0x0102\r\n", &[0x01, 0x02]),
+    ];
+    for &(input, expected) in ok {
+        let mut out = [0u8; 4];
+        let len = hex_to_bytes(input, &mut out);
+        assert_eq!(len, Ok(expected.len()), "{input:?}");
+        assert_eq!(&out[..expected.len()], expected, "{input:?}");
+    }
+
+    // Offsets count from the start of the untrimmed input
+    let err: &[(&str, crypto::HexError)] = &[
+        ("0g", InvalidChar(1)),
+        ("0x0g", InvalidChar(3)),
+        ("  0xzz", InvalidChar(4)),
+        ("00 ff", InvalidChar(2)),
+        ("0x00\tff", InvalidChar(4)),
+        ("x00", InvalidChar(0)),
+        ("0x0x00", InvalidChar(3)),
+        ("abc", OddLength),
+        ("0x1", OddLength),
+        // A bad character is reported ahead of the odd length
+        ("0x1g0", InvalidChar(3)),
+        ("0x0011223344", OutputTooSmall),
+    ];
+    for &(input, expected) in err {
+        let mut out = [0u8; 4];
+        assert_eq!(hex_to_bytes(input, &mut out), Err(expected), "{input:?}");
+    }
+}
+
+/// Domain separator of the "Ether Mail" example in the EIP-712 spec
+#[test]
+fn full_domain_matches_eip712_reference() {
+    let mut contract = [0u8; 20];
+    contract.copy_from_slice(&hex::decode("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap());
+    let domain = crypto::Eip712Domain::with_chain_id_u64("Ether Mail", "1", 1, contract);
+
+    assert_eq!(
+        hex::encode(domain.hash_struct()),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+}
+
+/// A chain ID above `u64::MAX` must reach the separator unchanged
+#[test]
+fn wide_chain_id_is_encoded_in_full() {
+    let mut contract = [0u8; 20];
+    contract.copy_from_slice(&hex::decode("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap());
+    let mut chain_id = [0u8; 32];
+    chain_id[23] = 1;
+    chain_id[31] = 5;
+    let domain = crypto::Eip712Domain::new("Ether Mail", "1", chain_id, contract);
+
+    assert_eq!(domain.chain_id, Some(chain_id));
+    assert_eq!(
+        hex::encode(domain.hash_struct()),
+        "c91d47d336290d37d1dd2189100337f638cfca7820bb2cef1e25918ea804621f"
+    );
+}
+
+#[test]
+fn name_version_domain_matches_reference() {
+    let domain = crypto::Eip712Domain::name_version("Ether Mail", "1");
+
+    assert_eq!(
+        hex::encode(domain.type_hash()),
+        "b03948446334eb9b2196d5eb166f69b9d49403eb4a12f36de8d3f9f3cb8e15c3"
+    );
+    assert_eq!(
+        hex::encode(domain.hash_struct()),
+        "3672940656dbbfdd066ff6a32e08597dc0389bb88feb714e9eb8d8b151f25aec"
+    );
+}
+
+/// `PERMIT_TYPEHASH` as defined by EIP-2612 and OpenZeppelin's ERC20Permit
+#[test]
+fn permit_matches_eip2612_encoding() {
+    let mut owner = [0u8; 20];
+    owner.copy_from_slice(&hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap());
+    let mut spender = [0u8; 20];
+    spender.copy_from_slice(&hex::decode("2b5ad5c4795c026514f8317c7a215e218dccd6cf").unwrap());
+    let mut value = [0u8; 32];
+    value[24..].copy_from_slice(&1_000_000_000_000_000_000u64.to_be_bytes());
+
+    let permit = crypto::Permit {
+        owner,
+        spender,
+        value,
+        nonce: 0,
+        deadline: 1 << 32,
+    };
+
+    assert_eq!(
+        hex::encode(crypto::Permit::type_hash()),
+        "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9"
+    );
+    assert_eq!(
+        hex::encode(permit.hash_struct()),
+        "d1bf85f5aab075f8a3db1fad41125ffa906d70e180e19b20ca69712eac4b8673"
+    );
+}
+
+/// Array members hash the concatenated 32-byte elements
+#[test]
+fn uint256_array_member_matches_reference() {
+    let word = |n: u128| {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&n.to_be_bytes());
+        word
+    };
+    let amounts = [
+        word(1_000_000_000_000_000_000),
+        word(250_000_000_000_000_000_000),
+        word(0),
+    ];
+
+    assert_eq!(
+        hex::encode(crypto::encode_uint256_array(&[])),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        hex::encode(crypto::encode_uint256_array(&amounts)),
+        "2b3baeb03d6a6091dfed475a57a13045e36f5f2a30b87b619af8961c078d8dd4"
+    );
+
+    let schedule = crypto::PaymentSchedule {
+        token: [0x11; 20],
+        payee: [0x22; 20],
+        amounts: &amounts,
+        nonce: 7,
+    };
+    assert_eq!(
+        hex::encode(schedule.hash_struct()),
+        "3e64254618554a3ade3274068f905982a64a5f28d8bd7cfe7a9347c1f5866542"
+    );
+}
+
+proptest! {
+    #[test]
+    fn der_round_trip_is_stable(seed in any::<u32>(), message in proptest::collection::vec(any::<u8>(), 0..64)) {
+        let mut rng = crypto::InsecureCryptoRng::new(crypto::DemoRng::new(seed));
+        let keypair = crypto::EthereumKeyPair::generate(&mut rng);
+        let signature = keypair.sign(&message);
+
+        let der = crypto::signature_to_der(&signature);
+        let decoded = crypto::signature_from_der(&der).unwrap();
+        prop_assert_eq!(decoded.to_bytes(), signature.to_bytes());
+        prop_assert_eq!(crypto::signature_to_der(&decoded), der);
+    }
+}
+
+/// Signing example from EIP-155: the transaction's signing payload under
+/// the private key 0x4646...46
+#[test]
+fn deterministic_signature_matches_eip155_example() {
+    let keypair = crypto::EthereumKeyPair::from_private_key_bytes(&[0x46; 32]).unwrap();
+    let payload = hex::decode(
+        "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080",
+    )
+    .unwrap();
+
+    let signature = keypair.sign_deterministic(&payload);
+    assert_eq!(
+        hex::encode(signature.to_bytes()),
+        "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276\
+         67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    );
+    assert_eq!(keypair.sign_deterministic(&payload), signature);
+}
+
+/// The EIP-155 example signature (v = 37, so parity 0) recovers its signer
+#[test]
+fn ecrecover_matches_eip155_example() {
+    use crypto::CryptoError;
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(
+        &hex::decode("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").unwrap(),
+    );
+    let mut sig = [0u8; 65];
+    sig[..64].copy_from_slice(
+        &hex::decode(
+            "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276\
+             67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        )
+        .unwrap(),
+    );
+
+    for v in [0, 27] {
+        sig[64] = v;
+        assert_eq!(
+            hex::encode(crypto::ecrecover(&hash, &sig).unwrap()),
+            "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+    }
+
+    sig[64] = 29;
+    assert_eq!(
+        crypto::ecrecover(&hash, &sig),
+        Err(CryptoError::InvalidRecoveryId)
+    );
+
+    let mut zero_r = sig;
+    zero_r[..32].fill(0);
+    zero_r[64] = 27;
+    assert_eq!(
+        crypto::ecrecover(&hash, &zero_r),
+        Err(CryptoError::InvalidSignature)
+    );
+
+    // n - s recovers the same key in Solidity but is rejected here (EIP-2)
+    let signature = k256::ecdsa::Signature::from_slice(&sig[..64]).unwrap();
+    let high_s = k256::ecdsa::Signature::from_scalars(signature.r(), -*signature.s()).unwrap();
+    let mut high = [0u8; 65];
+    high[..64].copy_from_slice(&high_s.to_bytes());
+    high[64] = 28;
+    assert_eq!(
+        crypto::ecrecover(&hash, &high),
+        Err(CryptoError::InvalidSignature)
+    );
+}
+
+/// Private key 1 controls the well-known address 0x7E5F...5Bdf
+#[test]
+fn private_key_import_derives_address() {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let keypair = crypto::EthereumKeyPair::from_private_key_bytes(&one).unwrap();
+    assert_eq!(
+        hex::encode(keypair.address),
+        "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+    );
+    assert_eq!(keypair.private_key_bytes(), one);
+
+    let from_hex = crypto::EthereumKeyPair::from_private_key_hex(
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+    )
+    .unwrap();
+    assert_eq!(from_hex.address, keypair.address);
+}
+
+#[test]
+fn zeroize_discards_the_private_key_and_address() {
+    let mut keypair = crypto::EthereumKeyPair::from_private_key_bytes(&[0x46; 32]).unwrap();
+    keypair.zeroize();
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    assert_eq!(keypair.private_key_bytes(), one);
+    assert_eq!(keypair.address, [0; 20]);
+    assert_eq!(keypair.public_key_compressed_bytes(), [0; 33]);
+}
+
+#[test]
+fn private_key_import_rejects_invalid_scalars() {
+    use crypto::{CryptoError, EthereumKeyPair, HexError};
+
+    let order = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+    for bad in [[0u8; 32], [0xFF; 32]] {
+        assert_eq!(
+            EthereumKeyPair::from_private_key_bytes(&bad).err(),
+            Some(CryptoError::InvalidPrivateKey)
+        );
+    }
+    assert_eq!(
+        EthereumKeyPair::from_private_key_hex(order).err(),
+        Some(CryptoError::InvalidPrivateKey)
+    );
+    assert_eq!(
+        EthereumKeyPair::from_private_key_hex("0x01").err(),
+        Some(CryptoError::InvalidLength)
+    );
+    assert_eq!(
+        EthereumKeyPair::from_private_key_hex(&order.repeat(2)).err(),
+        Some(CryptoError::InvalidLength)
+    );
+    assert_eq!(
+        EthereumKeyPair::from_private_key_hex("zz").err(),
+        Some(CryptoError::Hex(HexError::InvalidChar(0)))
+    );
+}
+
+/// Test vectors from the EIP-55 specification
+#[test]
+fn checksum_address_matches_eip55_vectors() {
+    for expected in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hex::decode(&expected[2..]).unwrap());
+        let mut out = [0u8; 42];
+        assert_eq!(crypto::to_checksum_address(&address, &mut out), expected);
+    }
+}
+
+/// Safe message hash built from Safe's published type hashes
+#[test]
+fn safe_message_hash_matches_reference() {
+    let safe = [0x11u8; 20];
+    let message_hash: [u8; 32] = core::array::from_fn(|i| i as u8);
+
+    assert_eq!(
+        hex::encode(crypto::safe_message_hash(&safe, 1, &message_hash)),
+        "ae247aad958a9f2801be94e02fdb6718d77effc5aeb182be435c65c28f9de8f8"
+    );
+}
+
+#[test]
+fn safe_message_signature_recovers_owner() {
+    let keypair = crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+        crypto::DemoRng::new(7),
+    ));
+    let safe = [0x22u8; 20];
+    let message_hash = crypto::keccak256(b"hello safe");
+
+    let (signature, recovery_id) =
+        crypto::sign_safe_message(&keypair, &safe, 5, &message_hash).unwrap();
+    let digest = crypto::safe_message_hash(&safe, 5, &message_hash);
+    assert_eq!(
+        crypto::recover_address_from_hash(&digest, &signature, recovery_id).unwrap(),
+        keypair.address
+    );
+}
+
+/// secp256k1 group order / 2, the largest low-s value
+const HALF_ORDER: &str = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+
+proptest! {
+    #[test]
+    fn recoverable_signature_recovers_signer(seed in any::<u32>(), message in proptest::collection::vec(any::<u8>(), 0..64)) {
+        let keypair = crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+            crypto::DemoRng::new(seed),
+        ));
+        let (signature, recovery_id) = keypair.sign_recoverable(&message);
+        prop_assert_eq!(
+            crypto::recover_address(&message, &signature, recovery_id).unwrap(),
+            keypair.address
+        );
+
+        let rsv = crypto::signature_to_eth_bytes(&signature, recovery_id);
+        prop_assert!(rsv[64] == 27 || rsv[64] == 28);
+        prop_assert!(hex::encode(&rsv[32..64]).as_str() <= HALF_ORDER);
+    }
+}
+
+#[test]
+fn verify_strict_rejects_high_s() {
+    use crypto::CryptoError;
+
+    let keypair = crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+        crypto::DemoRng::new(7),
+    ));
+    let message = b"strict";
+    let low = keypair.sign(message);
+    let high = k256::ecdsa::Signature::from_scalars(low.r(), -*low.s()).unwrap();
+
+    assert!(crypto::is_low_s(&low));
+    assert!(!crypto::is_low_s(&high));
+    assert_eq!(keypair.verify_strict(message, &low), Ok(()));
+    assert_eq!(
+        keypair.verify_strict(message, &high),
+        Err(CryptoError::MalleableSignature)
+    );
+    assert_eq!(
+        keypair.verify_strict(b"other", &low),
+        Err(CryptoError::InvalidSignature)
+    );
+}
+
+/// EIP-191 personal message hash, as used by the EIP-2098 examples
+fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    crypto::keccak256(&prefixed)
+}
+
+/// Test vectors from the EIP-2098 specification
+#[test]
+fn eip2098_matches_reference_vectors() {
+    use k256::ecdsa::{SigningKey, VerifyingKey};
+
+    let private_key =
+        hex::decode("1234567890123456789012345678901234567890123456789012345678901234").unwrap();
+    let signing_key = SigningKey::from_slice(&private_key).unwrap();
+
+    let vectors = [
+        (
+            "Hello World",
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+            "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            false,
+        ),
+        (
+            "It's a small(er) world",
+            "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76",
+            "139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+            "939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+            true,
+        ),
+    ];
+
+    for (message, r, s, y_parity_and_s, y_odd) in vectors {
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&hex::decode(r).unwrap());
+        compact[32..].copy_from_slice(&hex::decode(y_parity_and_s).unwrap());
+
+        let (signature, recovery_id) = crypto::signature_from_eip2098(&compact).unwrap();
+        assert_eq!(hex::encode(signature.s().to_bytes()), s);
+        assert_eq!(recovery_id.is_y_odd(), y_odd);
+
+        let hash = personal_message_hash(message.as_bytes());
+        let recovered = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id).unwrap();
+        assert_eq!(&recovered, signing_key.verifying_key());
+
+        assert_eq!(
+            crypto::signature_to_eip2098(&signature, recovery_id),
+            compact
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn eip2098_round_trip_preserves_rsv(seed in any::<u32>(), message in proptest::collection::vec(any::<u8>(), 0..64)) {
+        let keypair = crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+            crypto::DemoRng::new(seed),
+        ));
+        let (signature, recovery_id) = keypair.sign_recoverable(&message);
+
+        let compact = crypto::signature_to_eip2098(&signature, recovery_id);
+        let (decoded, decoded_id) = crypto::signature_from_eip2098(&compact).unwrap();
+        prop_assert_eq!(
+            crypto::signature_to_eth_bytes(&decoded, decoded_id),
+            crypto::signature_to_eth_bytes(&signature, recovery_id)
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn reveal_matches_only_its_commitment(
+        value in proptest::collection::vec(any::<u8>(), 0..64),
+        salt in any::<[u8; 32]>(),
+        flip in 0usize..32,
+    ) {
+        let commitment = crypto::commit(&value, &salt);
+        prop_assert!(crypto::verify_reveal(&commitment, &value, &salt));
+
+        let mut other_salt = salt;
+        other_salt[flip] ^= 1;
+        prop_assert!(!crypto::verify_reveal(&commitment, &value, &other_salt));
+    }
+}
+
+proptest! {
+    #[test]
+    fn addresses_equal_matches_eq(a in any::<[u8; 20]>(), b in any::<[u8; 20]>()) {
+        prop_assert!(crypto::addresses_equal(&a, &a));
+        prop_assert_eq!(crypto::addresses_equal(&a, &b), a == b);
+    }
+
+    #[test]
+    fn ct_eq_matches_eq(
+        a in proptest::collection::vec(any::<u8>(), 0..40),
+        b in proptest::collection::vec(any::<u8>(), 0..40),
+    ) {
+        prop_assert!(crypto::ct_eq(&a, &a));
+        prop_assert_eq!(crypto::ct_eq(&a, &b), a == b);
+        prop_assert!(!crypto::ct_eq(&a, &[a.as_slice(), &[0]].concat()));
+    }
+}
+
+#[test]
+fn multisig_verifier_counts_distinct_authorized_signers() {
+    use crypto::{CryptoError, MultisigVerifier};
+
+    let owners: Vec<_> = (1..=4)
+        .map(|seed| {
+            crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+                crypto::DemoRng::new(seed),
+            ))
+        })
+        .collect();
+    let addresses: Vec<_> = owners[..3].iter().map(|owner| owner.address).collect();
+    let verifier = MultisigVerifier::new(&addresses, 2).unwrap();
+
+    let domain = crypto::Eip712Domain::with_chain_id_u64("Multisig", "1", 1, [0x11; 20]);
+    let data = [0xAB; 4];
+    let struct_hash = MultisigTransaction::new([0x22; 20], 1, &data, 0).hash_struct();
+    let digest = crypto::eip712_hash(&domain, &struct_hash);
+    let sign = |owner: &crypto::EthereumKeyPair| {
+        let (signature, recovery_id) = owner.sign_hash_recoverable(&digest).unwrap();
+        crypto::signature_to_eth_bytes(&signature, recovery_id)
+    };
+    let [a, b, c, outsider] = [0, 1, 2, 3].map(|i| sign(&owners[i]));
+
+    let verify = |sigs: &[[u8; 65]]| verifier.verify_signatures(&domain, &struct_hash, sigs);
+    assert_eq!(verify(&[a, b]), Ok(2));
+    assert_eq!(verify(&[c, a, b]), Ok(3));
+    assert_eq!(verify(&[a]), Err(CryptoError::BelowThreshold));
+    assert_eq!(verify(&[a, a]), Err(CryptoError::DuplicateSigner));
+    assert_eq!(verify(&[a, outsider]), Err(CryptoError::UnauthorizedSigner));
+
+    assert!(MultisigVerifier::new(&addresses, 0).is_err());
+    assert!(MultisigVerifier::new(&[addresses[0], addresses[0]], 2).is_err());
+}
+
+#[test]
+fn multisig_verifier_scans_every_signer() {
+    use crypto::{CryptoError, MultisigVerifier};
+
+    let [owner, outsider] = [1, 2].map(|seed| {
+        crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+            crypto::DemoRng::new(seed),
+        ))
+    });
+    // Authorize an address one bit away from the outsider's, plus owners
+    // at both ends of the sorted list
+    let mut near = outsider.address;
+    near[19] ^= 1;
+    let verifier =
+        MultisigVerifier::new(&[[0x00; 20], near, owner.address, [0xFF; 20]], 1).unwrap();
+
+    let domain = crypto::Eip712Domain::with_chain_id_u64("Multisig", "1", 1, [0x11; 20]);
+    let struct_hash = MultisigTransaction::new([0x22; 20], 1, &[], 0).hash_struct();
+    let digest = crypto::eip712_hash(&domain, &struct_hash);
+    let sign = |keypair: &crypto::EthereumKeyPair| {
+        let (signature, recovery_id) = keypair.sign_hash_recoverable(&digest).unwrap();
+        crypto::signature_to_eth_bytes(&signature, recovery_id)
+    };
+
+    let verify = |sig| verifier.verify_signatures(&domain, &struct_hash, &[sig]);
+    assert_eq!(verify(sign(&owner)), Ok(1));
+    assert_eq!(
+        verify(sign(&outsider)),
+        Err(CryptoError::UnauthorizedSigner)
+    );
+}
+
+#[test]
+fn nonce_tracker_rejects_replays() {
+    use crypto::{CryptoError, NonceTracker};
+
+    let mut nonces = NonceTracker::new();
+    assert_eq!(nonces.last_seen(), None);
+    assert_eq!(nonces.check_and_advance(0), Ok(()));
+    assert_eq!(nonces.check_and_advance(0), Err(CryptoError::StaleNonce));
+    assert_eq!(nonces.check_and_advance(5), Ok(()));
+    assert_eq!(nonces.check_and_advance(3), Err(CryptoError::StaleNonce));
+    assert_eq!(nonces.last_seen(), Some(5));
+
+    let mut restored = NonceTracker::with_last_seen(nonces.last_seen().unwrap());
+    assert_eq!(restored.check_and_advance(5), Err(CryptoError::StaleNonce));
+    assert_eq!(restored.check_and_advance(6), Ok(()));
+}
+
+#[test]
+fn multisig_transaction_nonce_cannot_be_replayed() {
+    use crypto::{CryptoError, MultisigVerifier, NonceTracker};
+
+    let owner = crypto::EthereumKeyPair::generate(&mut crypto::InsecureCryptoRng::new(
+        crypto::DemoRng::new(1),
+    ));
+    let verifier = MultisigVerifier::new(&[owner.address], 1).unwrap();
+    let domain = crypto::Eip712Domain::with_chain_id_u64("Multisig", "1", 1, [0x11; 20]);
+    let sign = |tx: &MultisigTransaction| {
+        let digest = crypto::eip712_hash(&domain, &tx.hash_struct());
+        let (signature, recovery_id) = owner.sign_hash_recoverable(&digest).unwrap();
+        crypto::signature_to_eth_bytes(&signature, recovery_id)
+    };
+    let first = MultisigTransaction::new([0x22; 20], 1, &[], 1);
+    let second = MultisigTransaction::new([0x22; 20], 1, &[], 2);
+    let (first_sig, second_sig) = (sign(&first), sign(&second));
+
+    let mut nonces = NonceTracker::new();
+    let mut verify = |tx, sig| verifier.verify_transaction(&domain, tx, &[sig], &mut nonces);
+    assert_eq!(
+        verify(&first, second_sig),
+        Err(CryptoError::UnauthorizedSigner)
+    );
+    assert_eq!(verify(&first, first_sig), Ok(1));
+    assert_eq!(verify(&first, first_sig), Err(CryptoError::StaleNonce));
+    assert_eq!(verify(&second, second_sig), Ok(1));
+    assert_eq!(nonces.last_seen(), Some(2));
+}
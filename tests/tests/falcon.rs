@@ -0,0 +1,58 @@
+//! Host tests for the Falcon512 message-to-Word mapping and commitments
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+#[allow(dead_code)]
+#[path = "../../src/utils/falcon.rs"]
+mod falcon;
+
+use falcon::{hash_bytes_to_word, signature_commitment, Falcon512KeyPair};
+use proptest::prelude::*;
+
+fn hash(message: &[u8]) -> [u64; 4] {
+    hash_bytes_to_word(message).map(|felt| felt.as_int())
+}
+
+/// Trailing zero bytes used to vanish into the last packed element
+#[test]
+fn trailing_zeros_change_the_word() {
+    assert_ne!(hash(b""), hash(&[0]));
+    assert_ne!(hash(b"abc"), hash(b"abc\0"));
+    assert_ne!(hash(&[0; 7]), hash(&[0; 8]));
+}
+
+/// A short tail must not collide with its own padding byte
+#[test]
+fn padding_is_unambiguous() {
+    assert_ne!(
+        hash(&[0xAA; 6]),
+        hash(&[0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0x01])
+    );
+}
+
+proptest! {
+    #[test]
+    fn distinct_messages_give_distinct_words(
+        a in proptest::collection::vec(any::<u8>(), 0..64),
+        b in proptest::collection::vec(any::<u8>(), 0..64),
+    ) {
+        prop_assume!(a != b);
+        prop_assert_ne!(hash(&a), hash(&b));
+    }
+}
+
+#[test]
+fn signature_commitments_are_deterministic_and_distinct() {
+    let mut rng = crypto::DemoRng::new(1);
+    let keypair = Falcon512KeyPair::generate(&mut rng);
+
+    let first = keypair.sign(b"first message", &mut rng);
+    let second = keypair.sign(b"second message", &mut rng);
+
+    assert_eq!(
+        signature_commitment(&first),
+        signature_commitment(&first.clone())
+    );
+    assert_ne!(signature_commitment(&first), signature_commitment(&second));
+}
@@ -0,0 +1,125 @@
+//! Host tests for the COBS framing in src/utils
+//!
+//! Fixed vectors are the examples from the COBS paper (Cheshire and Baker)
+//! as listed on Wikipedia, with the trailing delimiter this encoder adds.
+
+#[allow(dead_code)]
+#[path = "../../src/utils/framing.rs"]
+mod framing;
+
+use framing::{decode_frame, encode_frame, max_encoded_len, FramingError, DELIMITER};
+use proptest::prelude::*;
+
+fn encode(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; max_encoded_len(data.len())];
+    let len = encode_frame(data, &mut out).unwrap();
+    out.truncate(len);
+    out
+}
+
+fn decode(frame: &[u8]) -> Result<Vec<u8>, FramingError> {
+    let mut out = vec![0u8; frame.len()];
+    let len = decode_frame(frame, &mut out)?;
+    out.truncate(len);
+    Ok(out)
+}
+
+proptest! {
+    #[test]
+    fn frames_round_trip(data in proptest::collection::vec(any::<u8>(), 0..1024)) {
+        let frame = encode(&data);
+        prop_assert!(frame.len() <= max_encoded_len(data.len()));
+        prop_assert_eq!(frame.last(), Some(&DELIMITER));
+        prop_assert!(!frame[..frame.len() - 1].contains(&DELIMITER));
+        prop_assert_eq!(decode(&frame).unwrap(), data.clone());
+        // The delimiter is optional on input
+        prop_assert_eq!(decode(&frame[..frame.len() - 1]).unwrap(), data);
+    }
+}
+
+#[test]
+fn reference_vectors() {
+    let cases: &[(&[u8], &[u8])] = &[
+        (&[], &[0x01, 0x00]),
+        (&[0x00], &[0x01, 0x01, 0x00]),
+        (&[0x00, 0x00], &[0x01, 0x01, 0x01, 0x00]),
+        (
+            &[0x11, 0x22, 0x00, 0x33],
+            &[0x03, 0x11, 0x22, 0x02, 0x33, 0x00],
+        ),
+        (
+            &[0x11, 0x22, 0x33, 0x44],
+            &[0x05, 0x11, 0x22, 0x33, 0x44, 0x00],
+        ),
+        (
+            &[0x11, 0x00, 0x00, 0x00],
+            &[0x02, 0x11, 0x01, 0x01, 0x01, 0x00],
+        ),
+    ];
+    for &(data, frame) in cases {
+        assert_eq!(encode(data), frame, "{data:02x?}");
+        assert_eq!(decode(frame).unwrap(), data, "{frame:02x?}");
+    }
+}
+
+/// Runs of 254 non-zero bytes fill a block; the 255th starts a new one
+#[test]
+fn full_blocks() {
+    let run_254: Vec<u8> = (1..=254).collect();
+    let frame = encode(&run_254);
+    assert_eq!(frame[0], 0xFF);
+    assert_eq!(&frame[1..255], &run_254[..]);
+    // A full block implies no zero, so an empty block closes the frame
+    assert_eq!(&frame[255..], &[0x01, 0x00]);
+    assert_eq!(frame.len(), max_encoded_len(254));
+    assert_eq!(decode(&frame).unwrap(), run_254);
+
+    let run_255: Vec<u8> = (1..=255).collect();
+    let frame = encode(&run_255);
+    assert_eq!(frame[0], 0xFF);
+    assert_eq!(&frame[255..], &[0x02, 0xFF, 0x00]);
+    assert_eq!(decode(&frame).unwrap(), run_255);
+}
+
+#[test]
+fn malformed_frames_are_rejected() {
+    let cases: &[&[u8]] = &[
+        // A zero inside a block
+        &[0x03, 0x11, 0x00, 0x33],
+        // A zero where a block code is expected
+        &[0x02, 0x11, 0x00, 0x02, 0x33],
+        // A block code promising more bytes than the frame holds
+        &[0x05, 0x11, 0x22],
+        &[0x05, 0x11, 0x22, 0x33, 0x00],
+        &[0xFF, 0x01],
+    ];
+    for &frame in cases {
+        assert_eq!(
+            decode(frame),
+            Err(FramingError::MalformedFrame),
+            "{frame:02x?}"
+        );
+    }
+}
+
+/// `max_encoded_len` is exactly what `encode_frame` needs up front
+#[test]
+fn buffer_bounds() {
+    for len in [0, 1, 253, 254, 255, 508, 1000] {
+        let data = vec![0xA5u8; len];
+        let mut out = vec![0u8; max_encoded_len(len)];
+        assert!(encode_frame(&data, &mut out).is_ok(), "{len}");
+        assert_eq!(
+            encode_frame(&data, &mut out[..max_encoded_len(len) - 1]),
+            Err(FramingError::OutputTooSmall),
+            "{len}"
+        );
+    }
+
+    let frame = encode(&[0x11, 0x22, 0x00, 0x33]);
+    assert_eq!(
+        decode_frame(&frame, &mut [0u8; 3]),
+        Err(FramingError::OutputTooSmall)
+    );
+    assert_eq!(decode_frame(&frame, &mut [0u8; 4]), Ok(4));
+}
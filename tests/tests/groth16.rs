@@ -0,0 +1,233 @@
+//! Byte-encoding tests for the Groth16 key, proof and public inputs
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+#[allow(dead_code)]
+#[path = "../../src/utils/groth16.rs"]
+mod groth16;
+#[allow(dead_code)]
+#[path = "../../src/utils/poseidon.rs"]
+mod poseidon;
+
+use ark_bn254::{Bn254, Fr};
+use groth16::{
+    deserialize_public_inputs, serialize_public_inputs, verify_proof, verify_proofs_batch,
+    vk_proof, Groth16Error, Proof, Vk,
+};
+
+fn sample() -> (vk_proof::Vk, Proof, Vec<Fr>) {
+    let (proof, inputs) = vk_proof::sample_proof();
+    let proof = Proof {
+        a: proof.a,
+        b: proof.b,
+        c: proof.c,
+    };
+    (vk_proof::verification_key(), proof, inputs)
+}
+
+fn as_vk(vk: &vk_proof::Vk) -> Vk<'_> {
+    Vk {
+        alpha_g1: vk.alpha_g1,
+        beta_g2: vk.beta_g2,
+        gamma_g2: vk.gamma_g2,
+        delta_g2: vk.delta_g2,
+        ic: &vk.ic,
+    }
+}
+
+#[test]
+fn sample_round_trips_and_verifies() {
+    let (vk, proof, inputs) = sample();
+
+    let vk = Vk::<Bn254>::deserialize_uncompressed(&as_vk(&vk).serialize_uncompressed()).unwrap();
+    let proof = Proof::<Bn254>::deserialize_uncompressed(&proof.serialize_uncompressed()).unwrap();
+    let inputs = deserialize_public_inputs(&serialize_public_inputs(&inputs)).unwrap();
+
+    assert!(verify_proof(&vk.as_vk(), &proof, &inputs).is_ok());
+}
+
+#[test]
+fn wrong_inputs_report_why_verification_failed() {
+    let (vk, proof, inputs) = sample();
+    let vk = as_vk(&vk);
+
+    assert_eq!(
+        verify_proof(&vk, &proof, &[]),
+        Err(Groth16Error::InvalidPublicInputLength {
+            expected: inputs.len(),
+            got: 0
+        })
+    );
+
+    let swapped = Proof {
+        a: proof.c,
+        b: proof.b,
+        c: proof.a,
+    };
+    assert_eq!(
+        verify_proof(&vk, &swapped, &inputs),
+        Err(Groth16Error::PairingMismatch)
+    );
+}
+
+#[test]
+fn batch_accepts_valid_proofs_and_rejects_any_bad_one() {
+    let (vk, proof, inputs) = sample();
+    let pvk = as_vk(&vk).prepare();
+    let mut rng = crypto::InsecureCryptoRng::new(crypto::DemoRng::new(7));
+
+    let proofs = [proof; 4];
+    let batch_inputs = [&inputs[..]; 4];
+    assert_eq!(
+        verify_proofs_batch(&pvk, &proofs, &batch_inputs, &mut rng),
+        Ok(())
+    );
+    assert_eq!(verify_proofs_batch(&pvk, &[], &[], &mut rng), Ok(()));
+
+    let mut with_bad = proofs;
+    with_bad[2] = Proof {
+        a: proof.c,
+        b: proof.b,
+        c: proof.a,
+    };
+    assert_eq!(
+        verify_proofs_batch(&pvk, &with_bad, &batch_inputs, &mut rng),
+        Err(Groth16Error::PairingMismatch)
+    );
+
+    assert_eq!(
+        verify_proofs_batch(&pvk, &proofs, &batch_inputs[..3], &mut rng),
+        Err(Groth16Error::BatchSizeMismatch {
+            proofs: 4,
+            inputs: 3
+        })
+    );
+    assert_eq!(
+        verify_proofs_batch(&pvk, &proofs[..1], &[&[]], &mut rng),
+        Err(Groth16Error::InvalidPublicInputLength {
+            expected: inputs.len(),
+            got: 0
+        })
+    );
+}
+
+#[test]
+fn truncated_or_padded_bytes_are_rejected() {
+    let (vk, proof, inputs) = sample();
+    let vk_bytes = as_vk(&vk).serialize_uncompressed();
+    let mut proof_bytes = proof.serialize_uncompressed();
+    let input_bytes = serialize_public_inputs(&inputs);
+
+    assert_eq!(
+        Vk::<Bn254>::deserialize_uncompressed(&vk_bytes[..vk_bytes.len() - 1]).err(),
+        Some(Groth16Error::Malformed)
+    );
+    assert_eq!(
+        deserialize_public_inputs::<Fr>(&input_bytes[..input_bytes.len() - 1]).err(),
+        Some(Groth16Error::Malformed)
+    );
+    proof_bytes.push(0);
+    assert_eq!(
+        Proof::<Bn254>::deserialize_uncompressed(&proof_bytes).err(),
+        Some(Groth16Error::Malformed)
+    );
+}
+
+#[test]
+fn off_curve_point_is_rejected() {
+    let (_, proof, _) = sample();
+    let mut bytes = proof.serialize_uncompressed();
+    bytes[0] ^= 1; // Perturb the x coordinate of `a`
+
+    assert_eq!(
+        Proof::<Bn254>::deserialize_uncompressed(&bytes).err(),
+        Some(Groth16Error::PointNotOnCurve)
+    );
+}
+
+#[test]
+fn verification_rejects_points_off_curve_or_outside_subgroup() {
+    use ark_ec::AffineCurve;
+    use ark_ff::One;
+
+    let (vk, proof, inputs) = sample();
+    let vk = as_vk(&vk);
+
+    let mut off_curve = proof;
+    off_curve.a.y += ark_bn254::Fq::one();
+    assert_eq!(
+        verify_proof(&vk, &off_curve, &inputs),
+        Err(Groth16Error::PointNotOnCurve)
+    );
+
+    // A point on the G2 twist is almost never in the prime-order subgroup
+    let mut x = ark_bn254::Fq2::one();
+    let twist_point = loop {
+        if let Some(point) = ark_bn254::G2Affine::get_point_from_x(x, false) {
+            break point;
+        }
+        x += ark_bn254::Fq2::one();
+    };
+    let mut small_subgroup = proof;
+    small_subgroup.b = twist_point.into_projective();
+    assert_eq!(
+        verify_proof(&vk, &small_subgroup, &inputs),
+        Err(Groth16Error::PointNotInSubgroup)
+    );
+    assert_eq!(
+        groth16::validate_proof(&small_subgroup),
+        Err(Groth16Error::PointNotInSubgroup)
+    );
+    assert_eq!(groth16::validate_proof(&proof), Ok(()));
+}
+
+#[test]
+fn bls12_381_proof_verifies() {
+    use ark_bls12_381::{Bls12_381, Fr as BlsFr, G1Projective, G2Projective};
+    use ark_ec::ProjectiveCurve;
+    use ark_ff::PrimeField;
+
+    // With every exponent known, a valid proof can be built without a prover:
+    // A = s*G1, B = G2 and C chosen so s = alpha*beta + (ic0 + x*ic1) + c
+    let g1 = G1Projective::prime_subgroup_generator();
+    let g2 = G2Projective::prime_subgroup_generator();
+    let (alpha, beta, ic0, ic1, s) = (
+        BlsFr::from(11u64),
+        BlsFr::from(13u64),
+        BlsFr::from(17u64),
+        BlsFr::from(19u64),
+        BlsFr::from(23u64),
+    );
+    let x = BlsFr::from(5u64);
+    let c = s - alpha * beta - (ic0 + x * ic1);
+
+    let ic = [g1.mul(ic0.into_repr()), g1.mul(ic1.into_repr())];
+    let vk = Vk::<Bls12_381> {
+        alpha_g1: g1.mul(alpha.into_repr()),
+        beta_g2: g2.mul(beta.into_repr()),
+        gamma_g2: g2,
+        delta_g2: g2,
+        ic: &ic,
+    };
+    let proof = Proof::<Bls12_381> {
+        a: g1.mul(s.into_repr()),
+        b: g2,
+        c: g1.mul(c.into_repr()),
+    };
+
+    assert_eq!(verify_proof(&vk, &proof, &[x]), Ok(()));
+    assert_eq!(
+        verify_proof(&vk, &proof, &[x + BlsFr::from(1u64)]),
+        Err(Groth16Error::PairingMismatch)
+    );
+
+    let decoded = Vk::<Bls12_381>::deserialize_uncompressed(&vk.serialize_uncompressed()).unwrap();
+    let pvk = decoded.as_vk().prepare();
+    assert_eq!(pvk.validate(), Ok(()));
+    let mut rng = crypto::InsecureCryptoRng::new(crypto::DemoRng::new(7));
+    assert_eq!(
+        verify_proofs_batch(&pvk, &[proof; 3], &[&[x][..]; 3], &mut rng),
+        Ok(())
+    );
+}
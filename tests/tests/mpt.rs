@@ -0,0 +1,79 @@
+//! Host tests for the Merkle Patricia trie proof verifier in src/utils
+//!
+//! The vectors are the `doe`/`dog`/`dogglesworth` trie from the Ethereum
+//! trie tests (root 8aad78...68d3). It covers an extension, branches with a
+//! value slot, an embedded (inline) leaf and hash-referenced nodes.
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+#[allow(dead_code)]
+#[path = "../../src/utils/mpt.rs"]
+mod mpt;
+#[allow(dead_code)]
+#[path = "../../src/utils/rlp.rs"]
+mod rlp;
+
+use mpt::{verify_mpt_proof, MptError};
+
+const ROOT: &str = "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3";
+
+// Root extension, branch holding `doe` inline, branch holding `dog`
+const PROOF_DOG: [&str; 3] = [
+    "e5831646f6a0db6ae1fda66890f6693f36560d36b4dca68b4d838f17016b151efe1d4c95c453",
+    "f83b8080808080ca20887265696e6465657280a037efd11993cb04a54048c25320e9f29c50a432d28afdf01598b2978ce1ca3068808080808080808080",
+    "e4808080808080ce89376c6573776f72746883636174808080808080808080857075707079",
+];
+
+fn root() -> [u8; 32] {
+    hex::decode(ROOT).unwrap().try_into().unwrap()
+}
+
+fn proof(nodes: &[&str]) -> Vec<Vec<u8>> {
+    nodes.iter().map(|n| hex::decode(n).unwrap()).collect()
+}
+
+#[test]
+fn proves_inclusion() {
+    let nodes = proof(&PROOF_DOG);
+    let dog = verify_mpt_proof(&root(), b"dog", &nodes).unwrap();
+    assert_eq!(dog.as_deref(), Some(&b"puppy"[..]));
+
+    // Same path, continuing into the inline leaf
+    let long = verify_mpt_proof(&root(), b"dogglesworth", &nodes).unwrap();
+    assert_eq!(long.as_deref(), Some(&b"cat"[..]));
+
+    let doe = verify_mpt_proof(&root(), b"doe", &nodes[..2]).unwrap();
+    assert_eq!(doe.as_deref(), Some(&b"reindeer"[..]));
+}
+
+#[test]
+fn proves_exclusion() {
+    let nodes = proof(&PROOF_DOG);
+    assert_eq!(verify_mpt_proof(&root(), b"doge", &nodes), Ok(None));
+    assert_eq!(verify_mpt_proof(&root(), b"cat", &nodes[..1]), Ok(None));
+    assert_eq!(verify_mpt_proof(&root(), b"do", &nodes[..1]), Ok(None));
+}
+
+#[test]
+fn rejects_invalid_proofs() {
+    let mut nodes = proof(&PROOF_DOG);
+    assert_eq!(
+        verify_mpt_proof(&root(), b"dog", &nodes[..2]),
+        Err(MptError::MissingNode)
+    );
+
+    let last = nodes[2].len() - 1;
+    nodes[2][last] ^= 1;
+    assert_eq!(
+        verify_mpt_proof(&root(), b"dog", &nodes),
+        Err(MptError::HashMismatch)
+    );
+
+    let mut wrong_root = root();
+    wrong_root[0] ^= 1;
+    assert_eq!(
+        verify_mpt_proof(&wrong_root, b"dog", &proof(&PROOF_DOG)),
+        Err(MptError::HashMismatch)
+    );
+}
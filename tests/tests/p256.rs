@@ -0,0 +1,72 @@
+//! Host tests for the P-256 key pair in src/utils
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto_p256.rs"]
+mod crypto_p256;
+
+use crypto::{CryptoError, DemoRng, InsecureCryptoRng};
+use crypto_p256::P256KeyPair;
+
+fn unhex<const N: usize>(hex: &str) -> [u8; N] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+/// RFC 6979 appendix A.2.5, P-256 with SHA-256, message "sample"
+#[test]
+fn sign_matches_rfc6979_vector() {
+    let keypair = P256KeyPair::from_private_key_bytes(&unhex(
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        hex::encode(keypair.public_key_uncompressed_bytes()),
+        "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+         7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+    );
+    assert_eq!(
+        hex::encode(keypair.public_key_compressed_bytes()),
+        "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+    );
+
+    let signature = keypair.sign(b"sample");
+    assert_eq!(
+        hex::encode(signature.to_bytes()),
+        "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+         f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+    );
+    assert!(keypair.verify(b"sample", &signature).is_ok());
+    assert!(keypair.verify(b"test", &signature).is_err());
+}
+
+#[test]
+fn generated_keys_round_trip_and_zeroize() {
+    let mut rng = InsecureCryptoRng::new(DemoRng::new(7));
+    let mut keypair = P256KeyPair::generate(&mut rng);
+
+    let imported = P256KeyPair::from_private_key_bytes(&keypair.private_key_bytes()).unwrap();
+    assert_eq!(
+        imported.public_key_compressed_bytes(),
+        keypair.public_key_compressed_bytes()
+    );
+
+    keypair.zeroize();
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    assert_eq!(keypair.private_key_bytes(), one);
+    assert_eq!(keypair.public_key_compressed_bytes(), [0; 33]);
+}
+
+#[test]
+fn import_rejects_invalid_scalars() {
+    let order = unhex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+    for key in [[0u8; 32], order, [0xFF; 32]] {
+        assert_eq!(
+            P256KeyPair::from_private_key_bytes(&key).err(),
+            Some(CryptoError::InvalidPrivateKey)
+        );
+    }
+}
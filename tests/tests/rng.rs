@@ -0,0 +1,147 @@
+//! Tests for the entropy pool in src/utils
+
+// The ADC helper is behind the firmware's `stm32f4` feature
+#[allow(dead_code, unexpected_cfgs)]
+#[path = "../../src/utils/rng.rs"]
+mod rng;
+
+use rng::{collect_entropy, EntropyPool};
+
+fn stirred_pool() -> EntropyPool {
+    let mut pool = EntropyPool::new();
+    pool.stir(b"trng");
+    pool.stir_u32(0xDEAD_BEEF);
+    pool
+}
+
+/// Pins the construction: `keccak256(tag || state || data)` chained as in rng.rs
+#[test]
+fn output_matches_reference() {
+    let mut pool = stirred_pool();
+    assert_eq!(pool.stirred_bytes(), 8);
+
+    let mut out = [0u8; 40];
+    pool.fill(&mut out);
+    assert_eq!(
+        hex::encode(out),
+        "c711739fad825c6154078ae909b6089c0d5b128d900b15c9f12174f60ef112a07cb2d336fd0346e9"
+    );
+
+    let mut next = [0u8; 8];
+    pool.fill(&mut next);
+    assert_eq!(hex::encode(next), "06f6143668a5a915");
+}
+
+#[test]
+fn every_stir_changes_the_output() {
+    let fill = |pool: &mut EntropyPool| {
+        let mut out = [0u8; 32];
+        pool.fill(&mut out);
+        out
+    };
+
+    let base = fill(&mut stirred_pool());
+
+    let mut extra = stirred_pool();
+    extra.stir(&[0]);
+    assert_ne!(fill(&mut extra), base);
+
+    // Splitting the same bytes over two stirs is a different input
+    let mut split = EntropyPool::new();
+    split.stir(b"tr");
+    split.stir(b"ng");
+    split.stir_u32(0xDEAD_BEEF);
+    assert_ne!(fill(&mut split), base);
+
+    // A weak source stirred in later does not undo the earlier ones
+    let mut weak = stirred_pool();
+    weak.stir(&[0; 64]);
+    assert_ne!(fill(&mut weak), fill(&mut EntropyPool::new()));
+}
+
+#[test]
+fn fills_never_repeat() {
+    let mut pool = stirred_pool();
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    pool.fill(&mut first);
+    pool.fill(&mut second);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn pool_feeds_key_generation() {
+    let mut pool = stirred_pool();
+    pool.stir(&[0x5A; 24]);
+    assert!(pool.is_seeded());
+    let first = k256::ecdsa::SigningKey::random(&mut pool);
+    let second = k256::ecdsa::SigningKey::random(&mut pool);
+    assert_ne!(first.to_bytes(), second.to_bytes());
+}
+
+/// An unseeded pool must not hand out its fixed output as key material
+#[test]
+fn unseeded_pool_refuses_to_serve_as_an_rng() {
+    use k256::elliptic_curve::rand_core::RngCore;
+
+    let mut pool = stirred_pool();
+    assert!(!pool.is_seeded());
+    assert!(pool.try_fill_bytes(&mut [0u8; 32]).is_err());
+
+    let result =
+        std::panic::catch_unwind(|| k256::ecdsa::SigningKey::random(&mut EntropyPool::new()));
+    assert!(result.is_err());
+}
+
+fn pool_output(pool: &mut EntropyPool) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    pool.fill(&mut out);
+    out
+}
+
+#[test]
+fn debiasing_keeps_only_differing_pairs() {
+    // Bit 0 alternates 0 then 1 (kept as 0), bit 1 goes 1 then 0 (kept as
+    // 1), and bit 2 and above are ignored
+    let readings = [0b110u16, 0b001, 0b010, 0b101, 0b011, 0b011];
+    let mut next = readings.iter().copied().cycle();
+    let mut pool = EntropyPool::new();
+    assert_eq!(
+        collect_entropy(|| next.next().unwrap(), readings.len(), &mut pool),
+        4
+    );
+
+    // Bits 0, 1, 0, 1 packed LSB first
+    let mut expected = EntropyPool::new();
+    expected.stir(&[0b1010]);
+    assert_eq!(pool_output(&mut pool), pool_output(&mut expected));
+}
+
+#[test]
+fn stuck_input_yields_nothing() {
+    let mut pool = EntropyPool::new();
+    assert_eq!(collect_entropy(|| 0x0ABC, 1000, &mut pool), 0);
+    assert_eq!(pool.stirred_bytes(), 0);
+}
+
+#[test]
+fn full_batches_are_stirred_as_they_fill() {
+    // Every pair yields two bits, so 1000 pairs fill seven 32-byte batches
+    // and leave 208 bits (26 bytes) over
+    let mut toggle = false;
+    let mut pool = EntropyPool::new();
+    let bits = collect_entropy(
+        || {
+            toggle = !toggle;
+            if toggle {
+                0b11
+            } else {
+                0b00
+            }
+        },
+        2000,
+        &mut pool,
+    );
+    assert_eq!(bits, 2000);
+    assert_eq!(pool.stirred_bytes(), 250);
+}
@@ -0,0 +1,69 @@
+//! Vector tests for the amount formatter in src/utils
+
+#[allow(dead_code)]
+#[path = "../../src/utils/units.rs"]
+mod units;
+
+use units::{format_wei_as_ether, ETHER_DECIMALS, MAX_FORMATTED_LEN};
+
+fn word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn format(value: u128, decimals: u8) -> String {
+    let mut buf = [0u8; MAX_FORMATTED_LEN];
+    format_wei_as_ether(&word(value), decimals, &mut buf).to_string()
+}
+
+#[test]
+fn formats_ether_amounts() {
+    assert_eq!(format(0, ETHER_DECIMALS), "0.0");
+    assert_eq!(format(1_000_000_000_000_000_000, ETHER_DECIMALS), "1.0");
+    assert_eq!(format(1_234_567_890_000_000_000, ETHER_DECIMALS), "1.2345");
+    assert_eq!(format(2_500_000_000_000_000, ETHER_DECIMALS), "0.0025");
+    assert_eq!(format(1, ETHER_DECIMALS), "0.000000000000000001");
+    assert_eq!(format(123_456_789, ETHER_DECIMALS), "0.0000000001234");
+    assert_eq!(
+        format(42_000_000_000_000_000_000_000, ETHER_DECIMALS),
+        "42000.0"
+    );
+}
+
+#[test]
+fn formats_other_decimals() {
+    assert_eq!(format(1_000_000, 6), "1.0");
+    assert_eq!(format(1_500_000, 6), "1.5");
+    assert_eq!(format(1_234, 0), "1234");
+    assert_eq!(format(5, 30), "0.000000000000000000000000000005");
+}
+
+#[test]
+fn formats_largest_value() {
+    let mut buf = [0u8; MAX_FORMATTED_LEN];
+    let max = format_wei_as_ether(&[0xff; 32], ETHER_DECIMALS, &mut buf);
+    assert_eq!(
+        max,
+        "115792089237316195423570985008687907853269984665640564039457.584"
+    );
+}
+
+/// Large `decimals` must not write past `MAX_FORMATTED_LEN`
+#[test]
+fn any_decimals_fit_the_buffer() {
+    assert_eq!(format(1, 78), format!("0.{}1", "0".repeat(77)));
+    assert_eq!(format(1, 79), "0.0");
+    assert_eq!(format(1, u8::MAX), "0.0");
+    assert_eq!(format(u128::MAX, u8::MAX), "0.0");
+
+    let mut buf = [0u8; MAX_FORMATTED_LEN];
+    assert_eq!(format_wei_as_ether(&[0xff; 32], 78, &mut buf), "0.1157");
+
+    for decimals in 0..=u8::MAX {
+        for value in [[0x00; 32], word(1), word(u128::MAX), [0xff; 32]] {
+            let mut buf = [0u8; MAX_FORMATTED_LEN];
+            format_wei_as_ether(&value, decimals, &mut buf);
+        }
+    }
+}
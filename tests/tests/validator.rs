@@ -0,0 +1,38 @@
+//! Host tests for the signature validators in src/utils
+
+#[allow(dead_code)]
+#[path = "../../src/utils/crypto.rs"]
+mod crypto;
+#[allow(dead_code)]
+#[path = "../../src/utils/validator.rs"]
+mod validator;
+
+use crypto::{
+    keccak256, signature_to_eip2098, signature_to_eth_bytes, DemoRng, EthereumKeyPair,
+    InsecureCryptoRng,
+};
+use validator::{EoaValidator, SignatureValidator};
+
+fn keypair(seed: u32) -> EthereumKeyPair {
+    EthereumKeyPair::generate(&mut InsecureCryptoRng::new(DemoRng::new(seed)))
+}
+
+#[test]
+fn eoa_validator_accepts_only_the_signer() {
+    let signer = keypair(1);
+    let hash = keccak256(b"authorize");
+    let (signature, recovery_id) = signer.sign_hash_recoverable(&hash).unwrap();
+
+    let mut rsv = signature_to_eth_bytes(&signature, recovery_id);
+    let compact = signature_to_eip2098(&signature, recovery_id);
+
+    let validator = EoaValidator::new(signer.address);
+    assert!(validator.is_valid(&hash, &rsv));
+    assert!(validator.is_valid(&hash, &compact));
+    assert!(!validator.is_valid(&keccak256(b"other"), &rsv));
+    assert!(!EoaValidator::new(keypair(2).address).is_valid(&hash, &rsv));
+
+    rsv[64] = 29;
+    assert!(!validator.is_valid(&hash, &rsv));
+    assert!(!validator.is_valid(&hash, &rsv[..10]));
+}